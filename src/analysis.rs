@@ -0,0 +1,218 @@
+use crate::lerp;
+use crate::types::*;
+use crate::ui::Painter;
+///root of g between a and b, where g(a) and g(b) differ in sign
+fn bisect(g: impl Fn(f64) -> f64, mut a: f64, mut b: f64) -> f64 {
+    let neg = g(a) < 0.0;
+    for _ in 0..48 {
+        let m = (a + b) * 0.5;
+        let gm = g(m);
+        if gm == 0.0 {
+            return m;
+        }
+        if (gm < 0.0) == neg { a = m } else { b = m }
+    }
+    (a + b) * 0.5
+}
+///maximum of g between a and b by golden section search
+fn golden(g: impl Fn(f64) -> f64, mut a: f64, mut b: f64) -> f64 {
+    let r = (5.0f64.sqrt() - 1.0) * 0.5;
+    let (mut c, mut d) = (b - r * (b - a), a + r * (b - a));
+    let (mut gc, mut gd) = (g(c), g(d));
+    for _ in 0..48 {
+        if gc > gd {
+            (b, d, gd) = (d, c, gc);
+            c = b - r * (b - a);
+            gc = g(c);
+        } else {
+            (a, c, gc) = (c, d, gd);
+            d = a + r * (b - a);
+            gd = g(d);
+        }
+    }
+    (a + b) * 0.5
+}
+///vertex of the parabola through three samples
+fn parabola((x0, y0): (f64, f64), (x1, y1): (f64, f64), (x2, y2): (f64, f64)) -> (f64, f64) {
+    let d = (x1 - x0) * (y1 - y2) - (x1 - x2) * (y1 - y0);
+    if d == 0.0 {
+        return (x1, y1);
+    }
+    let x = x1 - 0.5 * ((x1 - x0).powi(2) * (y1 - y2) - (x1 - x2).powi(2) * (y1 - y0)) / d;
+    let l = |xa: f64, xb: f64, xc: f64| (x - xb) * (x - xc) / ((xa - xb) * (xa - xc));
+    (
+        x,
+        y0 * l(x0, x1, x2) + y1 * l(x1, x0, x2) + y2 * l(x2, x0, x1),
+    )
+}
+impl Graph {
+    ///zeros, local extrema and pairwise intersections of the real part of every 2d series,
+    ///refined through point_eval if it is set, otherwise from the sampled data
+    pub(crate) fn get_analysis(&self) -> Vec<(Vec2, Feature)> {
+        let series = (0..self.data.len())
+            .map(|k| self.series_real(k))
+            .collect::<Vec<Vec<(f64, f64)>>>();
+        let f = |k: usize, x: f64| {
+            self.point_eval
+                .as_ref()
+                .and_then(|e| e(k, x).to_options().0)
+                .filter(|v| v.is_finite())
+                .unwrap_or_else(|| lerp(&series[k], x))
+        };
+        let mut out = Vec::new();
+        for (k, pts) in series.iter().enumerate() {
+            for (i, &(x0, y0)) in pts.iter().enumerate() {
+                if y0 == 0.0 {
+                    out.push((Vec2::new(x0, 0.0), Feature::Zero(k)));
+                } else if let Some(&(x1, y1)) = pts.get(i + 1)
+                    && y0 * y1 < 0.0
+                {
+                    let x = bisect(|x| f(k, x), x0, x1);
+                    out.push((Vec2::new(x, 0.0), Feature::Zero(k)));
+                }
+            }
+            for w in pts.windows(3) {
+                let (max, min) = (
+                    w[1].1 > w[0].1 && w[1].1 >= w[2].1,
+                    w[1].1 < w[0].1 && w[1].1 <= w[2].1,
+                );
+                if !max && !min {
+                    continue;
+                }
+                let p = if self.point_eval.is_some() {
+                    let s = if max { 1.0 } else { -1.0 };
+                    let x = golden(|x| s * f(k, x), w[0].0, w[2].0);
+                    Vec2::new(x, f(k, x))
+                } else {
+                    let (x, y) = parabola(w[0], w[1], w[2]);
+                    Vec2::new(x, y)
+                };
+                out.push((
+                    p,
+                    if max {
+                        Feature::Max(k)
+                    } else {
+                        Feature::Min(k)
+                    },
+                ));
+            }
+        }
+        for (a, pa) in series.iter().enumerate() {
+            for (b, pb) in series.iter().enumerate().skip(a + 1) {
+                let (Some(fa), Some(la), Some(fb), Some(lb)) =
+                    (pa.first(), pa.last(), pb.first(), pb.last())
+                else {
+                    continue;
+                };
+                let (lo, hi) = (fa.0.max(fb.0), la.0.min(lb.0));
+                let mut xs = pa
+                    .iter()
+                    .chain(pb.iter())
+                    .map(|p| p.0)
+                    .filter(|x| (lo..=hi).contains(x))
+                    .collect::<Vec<f64>>();
+                xs.sort_by(|x, y| x.total_cmp(y));
+                xs.dedup();
+                let d = |x: f64| f(a, x) - f(b, x);
+                for (i, &x0) in xs.iter().enumerate() {
+                    let d0 = d(x0);
+                    let x = if d0 == 0.0 {
+                        x0
+                    } else if let Some(&x1) = xs.get(i + 1)
+                        && d0 * d(x1) < 0.0
+                    {
+                        bisect(d, x0, x1)
+                    } else {
+                        continue;
+                    };
+                    out.push((Vec2::new(x, f(a, x)), Feature::Intersection(a, b)));
+                }
+            }
+        }
+        out
+    }
+    ///lines listing the analysis results for the sidebar
+    pub(crate) fn analysis_list(&self) -> Vec<String> {
+        self.analysis
+            .iter()
+            .flatten()
+            .map(|(p, f)| {
+                let name = match f {
+                    Feature::Zero(k) => format!("zero {}", self.series_name(*k)),
+                    Feature::Min(k) => format!("min {}", self.series_name(*k)),
+                    Feature::Max(k) => format!("max {}", self.series_name(*k)),
+                    Feature::Intersection(a, b) => format!(
+                        "intersection {} {}",
+                        self.series_name(*a),
+                        self.series_name(*b)
+                    ),
+                };
+                format!("{name} {:E},{:E}", p.x, p.y)
+            })
+            .collect()
+    }
+    ///steps the selected analysis result, moving the view to it if it is off screen
+    pub(crate) fn analysis_step(&mut self, step: isize) {
+        let Some(len) = self.analysis.as_ref().map(|a| a.len()).filter(|l| *l > 0) else {
+            return;
+        };
+        let i = match self.analysis_index {
+            Some(i) => (i as isize + step).rem_euclid(len as isize) as usize,
+            None if step < 0 => len - 1,
+            None => 0,
+        };
+        self.analysis_index = Some(i);
+        let p = self.analysis.as_ref().unwrap()[i].0;
+        if !self.in_screen(self.to_screen(p.x, p.y)) {
+            self.offset = self.get_new_offset(p);
+            self.recalculate(None);
+        }
+    }
+    pub(crate) fn write_analysis(&self, painter: &mut Painter) {
+        let Some(analysis) = &self.analysis else {
+            return;
+        };
+        let r = self.point_size;
+        for (n, (p, f)) in analysis.iter().enumerate() {
+            let s = self.to_screen(p.x, p.y);
+            if !self.in_screen(s) {
+                continue;
+            }
+            let mut line = |a: (f32, f32), b: (f32, f32)| {
+                painter.line_segment(
+                    [
+                        Pos::new(s.x + a.0 * r, s.y + a.1 * r),
+                        Pos::new(s.x + b.0 * r, s.y + b.1 * r),
+                    ],
+                    self.line_width,
+                    &self.text_color,
+                )
+            };
+            match f {
+                Feature::Zero(_) => painter.circle(s, r, &self.text_color, self.line_width),
+                Feature::Max(_) => {
+                    line((-1.0, 1.0), (0.0, -1.0));
+                    line((0.0, -1.0), (1.0, 1.0));
+                }
+                Feature::Min(_) => {
+                    line((-1.0, -1.0), (0.0, 1.0));
+                    line((0.0, 1.0), (1.0, -1.0));
+                }
+                Feature::Intersection(_, _) => {
+                    line((-1.0, -1.0), (1.0, 1.0));
+                    line((-1.0, 1.0), (1.0, -1.0));
+                }
+            }
+            if self.analysis_index == Some(n) {
+                painter.circle(s, 2.0 * r, &self.select_color, self.line_width);
+            }
+            self.text(
+                Pos::new(s.x + r, s.y - r),
+                Align::LeftBottom,
+                &format!("{:E},{:E}", p.x, p.y),
+                &self.text_color,
+                painter,
+            );
+        }
+    }
+}
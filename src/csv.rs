@@ -0,0 +1,166 @@
+use crate::types::*;
+///splits a line on the delimiter, keeping delimiters inside double quotes
+fn fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => out.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    out.push(field);
+    out
+}
+///the number in a field, none for empty or non numeric fields which are gaps in the data
+fn number(field: Option<&String>) -> Option<f64> {
+    field?.trim().parse::<f64>().ok()
+}
+impl GraphType {
+    ///parses delimited text into one Coord series per value column, or Coord3D if CsvOptions.y is set,
+    ///along with names taken from the header, or "column n" without one,
+    ///
+    ///missing or non numeric values are kept as gaps, lines starting with # are skipped
+    pub fn from_csv(
+        text: &str,
+        options: &CsvOptions,
+    ) -> Result<(Vec<GraphType>, Vec<Name>), String> {
+        let lines = text
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+            .collect::<Vec<&str>>();
+        let Some(first) = lines.first() else {
+            return Err("no data".to_string());
+        };
+        let delimiter = options.delimiter.unwrap_or_else(|| {
+            ['\t', ';', ',']
+                .into_iter()
+                .max_by_key(|d| first.matches(*d).count())
+                .filter(|d| first.contains(*d))
+                .unwrap_or(',')
+        });
+        let mut rows = lines
+            .iter()
+            .map(|l| fields(l, delimiter))
+            .collect::<Vec<Vec<String>>>();
+        let header = options.header.unwrap_or_else(|| {
+            rows[0]
+                .iter()
+                .any(|f| !f.trim().is_empty() && f.trim().parse::<f64>().is_err())
+        });
+        let header = if header { rows.remove(0) } else { Vec::new() };
+        let width = rows
+            .iter()
+            .chain(std::iter::once(&header))
+            .map(|r| r.len())
+            .max()
+            .unwrap_or(0);
+        let columns = if options.columns.is_empty() {
+            (0..width)
+                .filter(|c| options.x != Some(*c) && options.y != Some(*c))
+                .map(CsvColumn::Real)
+                .collect()
+        } else {
+            options.columns.clone()
+        };
+        if let Some(c) = columns
+            .iter()
+            .flat_map(|c| match c {
+                CsvColumn::Real(c) => vec![*c],
+                CsvColumn::Complex(a, b) => vec![*a, *b],
+            })
+            .chain(options.x)
+            .chain(options.y)
+            .find(|c| *c >= width)
+        {
+            return Err(format!("column {c} out of range"));
+        }
+        let name = |c: usize| {
+            header
+                .get(c)
+                .map(|h| h.trim().to_string())
+                .filter(|h| !h.is_empty())
+                .unwrap_or_else(|| format!("column {c}"))
+        };
+        let value = |row: &Vec<String>, c: &CsvColumn| match c {
+            CsvColumn::Real(c) => Complex::Real(number(row.get(*c)).unwrap_or(f64::NAN)),
+            CsvColumn::Complex(a, b) => match (number(row.get(*a)), number(row.get(*b))) {
+                (Some(a), Some(b)) => Complex::Complex(a, b),
+                _ => Complex::Complex(f64::NAN, f64::NAN),
+            },
+        };
+        let x = |i: usize, row: &Vec<String>| match options.x {
+            Some(c) => number(row.get(c)).unwrap_or(f64::NAN),
+            None => i as f64,
+        };
+        let data = columns
+            .iter()
+            .map(|c| match options.y {
+                Some(y) => GraphType::Coord3D(
+                    rows.iter()
+                        .enumerate()
+                        .map(|(i, row)| {
+                            (
+                                x(i, row),
+                                number(row.get(y)).unwrap_or(f64::NAN),
+                                value(row, c),
+                            )
+                        })
+                        .collect(),
+                ),
+                None => GraphType::Coord(
+                    rows.iter()
+                        .enumerate()
+                        .map(|(i, row)| (x(i, row), value(row, c)))
+                        .collect(),
+                ),
+            })
+            .collect();
+        let names = columns
+            .iter()
+            .map(|c| match c {
+                CsvColumn::Real(c) => Name {
+                    vars: Vec::new(),
+                    name: name(*c),
+                    show: Show::Real,
+                },
+                CsvColumn::Complex(c, _) => Name {
+                    vars: Vec::new(),
+                    name: name(*c),
+                    show: Show::Complex,
+                },
+            })
+            .collect();
+        Ok((data, names))
+    }
+}
+impl Graph {
+    ///reads a csv or tsv file with GraphType::from_csv and adds its series after the current data,
+    ///named after the file and column like "data.csv voltage"
+    pub fn load_csv(&mut self, path: &str, options: &CsvOptions) -> Result<(), String> {
+        let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let (data, names) = GraphType::from_csv(&text, options)?;
+        let file = std::path::Path::new(path)
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.names.extend(names.into_iter().map(|mut n| {
+            n.name = format!("{file} {}", n.name);
+            n
+        }));
+        self.data.extend(data);
+        self.cache = None;
+        self.analysis = None;
+        self.fit_results = None;
+        self.seams = None;
+        self.reset_3d_if_changed();
+        Ok(())
+    }
+}
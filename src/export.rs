@@ -0,0 +1,145 @@
+use crate::types::*;
+///a csv field, quoted if it contains a delimiter or quote
+fn field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+///a csv number, empty if it is missing or not finite
+fn number(v: Option<f64>) -> String {
+    v.filter(|v| v.is_finite())
+        .map(|v| v.to_string())
+        .unwrap_or_default()
+}
+impl Graph {
+    ///samples of the kth series including Point data, as x, y if the series is 3d, and the value,
+    ///only those inside the visible window if visible
+    fn export_points(&self, k: usize, visible: bool) -> Vec<(f64, Option<f64>, Complex)> {
+        fn push(data: &GraphType, pts: &mut Vec<(f64, Option<f64>, Complex)>) {
+            match data {
+                GraphType::Point(p) => pts.push((p.x, None, Complex::Real(p.y))),
+                GraphType::List(a) => a.iter().for_each(|data| push(data, pts)),
+                _ => {}
+            }
+        }
+        let mut pts = self.series_points(k);
+        if let Some(data) = self.data.get(k) {
+            push(data, &mut pts)
+        }
+        if visible {
+            let (l, t) = self.to_coord(Pos::new(0.0, 0.0));
+            let (r, b) = self.to_coord(self.screen.to_pos());
+            let (x0, x1) = (
+                self.bound.x / self.zoom_3d.x + self.offset3d.x,
+                self.bound.y / self.zoom_3d.x + self.offset3d.x,
+            );
+            let (y0, y1) = (
+                self.bound.x / self.zoom_3d.y - self.offset3d.y,
+                self.bound.y / self.zoom_3d.y - self.offset3d.y,
+            );
+            let (z0, z1) = (
+                self.bound.x / self.zoom_3d.z - self.offset3d.z,
+                self.bound.y / self.zoom_3d.z - self.offset3d.z,
+            );
+            pts.retain(|(x, y, z)| {
+                let (re, im) = z.to_options();
+                match y {
+                    Some(y) => {
+                        (x0..=x1).contains(x)
+                            && (y0..=y1).contains(y)
+                            && [re, im].iter().flatten().any(|v| (z0..=z1).contains(v))
+                    }
+                    None if self.graph_mode == GraphMode::Flatten => {
+                        (l..=r).contains(&re.unwrap_or(0.0)) && (b..=t).contains(&im.unwrap_or(0.0))
+                    }
+                    None => {
+                        (l..=r).contains(x)
+                            && [re, im].iter().flatten().any(|v| (b..=t).contains(v))
+                    }
+                }
+            })
+        }
+        pts
+    }
+    ///writes every series with its name as csv or json, with the x input,
+    ///the y input for 3d data, and the real and imaginary parts of each sample,
+    ///only samples inside the visible window if visible
+    pub fn export_data(&self, format: ExportFormat, visible: bool) -> String {
+        let series = (0..self.data.len())
+            .map(|k| (self.series_name(k), self.export_points(k, visible)))
+            .collect::<Vec<(&str, Vec<(f64, Option<f64>, Complex)>)>>();
+        match format {
+            ExportFormat::Csv => {
+                let d3 = series
+                    .iter()
+                    .any(|(_, pts)| pts.iter().any(|(_, y, _)| y.is_some()));
+                let mut lines = vec![if d3 {
+                    "series,name,x,y,re,im".to_string()
+                } else {
+                    "series,name,x,re,im".to_string()
+                }];
+                for (k, (name, pts)) in series.iter().enumerate() {
+                    let name = field(name);
+                    lines.extend(pts.iter().map(|(x, y, z)| {
+                        let (re, im) = z.to_options();
+                        if d3 {
+                            format!(
+                                "{k},{name},{},{},{},{}",
+                                number(Some(*x)),
+                                number(*y),
+                                number(re),
+                                number(im)
+                            )
+                        } else {
+                            format!(
+                                "{k},{name},{},{},{}",
+                                number(Some(*x)),
+                                number(re),
+                                number(im)
+                            )
+                        }
+                    }))
+                }
+                lines.join("\n")
+            }
+            #[cfg(feature = "serde")]
+            ExportFormat::Json => {
+                let series = series
+                    .iter()
+                    .map(|(name, pts)| {
+                        let mut obj = serde_json::Map::new();
+                        obj.insert("name".to_string(), name.to_string().into());
+                        obj.insert(
+                            "x".to_string(),
+                            pts.iter().map(|p| p.0).collect::<Vec<f64>>().into(),
+                        );
+                        if pts.iter().any(|(_, y, _)| y.is_some()) {
+                            obj.insert(
+                                "y".to_string(),
+                                pts.iter().map(|p| p.1).collect::<Vec<Option<f64>>>().into(),
+                            );
+                        }
+                        obj.insert(
+                            "re".to_string(),
+                            pts.iter()
+                                .map(|p| p.2.to_options().0)
+                                .collect::<Vec<Option<f64>>>()
+                                .into(),
+                        );
+                        obj.insert(
+                            "im".to_string(),
+                            pts.iter()
+                                .map(|p| p.2.to_options().1)
+                                .collect::<Vec<Option<f64>>>()
+                                .into(),
+                        );
+                        serde_json::Value::Object(obj)
+                    })
+                    .collect::<Vec<serde_json::Value>>();
+                serde_json::to_string_pretty(&series).unwrap_or_default()
+            }
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use crate::types::*;
+///solves a x = b by gaussian elimination with partial pivoting
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for i in 0..n {
+        let p = (i..n).max_by(|x, y| a[*x][i].abs().total_cmp(&a[*y][i].abs()))?;
+        if a[p][i].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(i, p);
+        b.swap(i, p);
+        let (top, bottom) = a.split_at_mut(i + 1);
+        for (j, row) in bottom.iter_mut().enumerate() {
+            let f = row[i] / top[i][i];
+            for (r, p) in row[i..].iter_mut().zip(&top[i][i..]) {
+                *r -= f * p;
+            }
+            b[i + 1 + j] -= f * b[i];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let s = (i + 1..n).map(|k| a[i][k] * x[k]).sum::<f64>();
+        x[i] = (b[i] - s) / a[i][i];
+    }
+    x.iter().all(|x| x.is_finite()).then_some(x)
+}
+///least squares polynomial coefficients, lowest degree first
+fn polyfit(pts: &[(f64, f64)], degree: usize) -> Option<Vec<f64>> {
+    let n = degree + 1;
+    if pts.len() < n {
+        return None;
+    }
+    let mut a = vec![vec![0.0; n]; n];
+    let mut b = vec![0.0; n];
+    for (x, y) in pts {
+        let pows = (0..2 * n).map(|i| x.powi(i as i32)).collect::<Vec<f64>>();
+        for i in 0..n {
+            for j in 0..n {
+                a[i][j] += pows[i + j];
+            }
+            b[i] += pows[i] * y;
+        }
+    }
+    solve(a, b)
+}
+///least squares parameters of a user model by levenberg marquardt with a numeric jacobian
+fn lmfit(pts: &[(f64, f64)], f: &dyn Fn(&[f64], f64) -> f64, init: &[f64]) -> Option<Vec<f64>> {
+    let n = init.len();
+    let mut p = init.to_vec();
+    let cost = |p: &[f64]| pts.iter().map(|(x, y)| (y - f(p, *x)).powi(2)).sum::<f64>();
+    let mut c = cost(&p);
+    let mut lambda = 1e-3;
+    for _ in 0..128 {
+        let jac = pts
+            .iter()
+            .map(|(x, _)| {
+                (0..n)
+                    .map(|j| {
+                        let h = 1e-7 * p[j].abs().max(1.0);
+                        let mut q = p.clone();
+                        q[j] += h;
+                        (f(&q, *x) - f(&p, *x)) / h
+                    })
+                    .collect::<Vec<f64>>()
+            })
+            .collect::<Vec<Vec<f64>>>();
+        let mut a = vec![vec![0.0; n]; n];
+        let mut b = vec![0.0; n];
+        for ((x, y), row) in pts.iter().zip(&jac) {
+            let r = y - f(&p, *x);
+            for i in 0..n {
+                for j in 0..n {
+                    a[i][j] += row[i] * row[j];
+                }
+                b[i] += row[i] * r;
+            }
+        }
+        for (i, row) in a.iter_mut().enumerate() {
+            row[i] *= 1.0 + lambda;
+        }
+        let Some(step) = solve(a, b) else {
+            lambda *= 10.0;
+            continue;
+        };
+        let q = p
+            .iter()
+            .zip(&step)
+            .map(|(p, s)| p + s)
+            .collect::<Vec<f64>>();
+        let nc = cost(&q);
+        if nc.is_finite() && nc < c {
+            let done = (c - nc) <= 1e-15 * c;
+            (p, c) = (q, nc);
+            lambda /= 10.0;
+            if done {
+                break;
+            }
+        } else {
+            lambda *= 10.0;
+        }
+    }
+    p.iter().all(|p| p.is_finite()).then_some(p)
+}
+impl FitModel {
+    pub fn eval(&self, c: &[f64], x: f64) -> f64 {
+        match self {
+            FitModel::Linear | FitModel::Polynomial(_) => {
+                c.iter().rev().fold(0.0, |acc, c| acc * x + c)
+            }
+            FitModel::Exponential => c[0] * (c[1] * x).exp(),
+            FitModel::Power => c[0] * x.powf(c[1]),
+            FitModel::Custom(f, _) => f(c, x),
+        }
+    }
+    ///coefficients fitted to the points by least squares,
+    ///exponential and power laws are fitted on the logarithm of the positive points
+    pub fn fit(&self, pts: &[(f64, f64)]) -> Option<Fit> {
+        let coefficients = match self {
+            FitModel::Linear => polyfit(pts, 1)?,
+            FitModel::Polynomial(n) => polyfit(pts, *n)?,
+            FitModel::Exponential => {
+                let logs = pts
+                    .iter()
+                    .filter(|(_, y)| *y > 0.0)
+                    .map(|(x, y)| (*x, y.ln()))
+                    .collect::<Vec<(f64, f64)>>();
+                let c = polyfit(&logs, 1)?;
+                vec![c[0].exp(), c[1]]
+            }
+            FitModel::Power => {
+                let logs = pts
+                    .iter()
+                    .filter(|(x, y)| *x > 0.0 && *y > 0.0)
+                    .map(|(x, y)| (x.ln(), y.ln()))
+                    .collect::<Vec<(f64, f64)>>();
+                let c = polyfit(&logs, 1)?;
+                vec![c[0].exp(), c[1]]
+            }
+            FitModel::Custom(f, init) => lmfit(pts, f.as_ref(), init)?,
+        };
+        let mean = pts.iter().map(|(_, y)| y).sum::<f64>() / pts.len() as f64;
+        let (res, tot) = pts.iter().fold((0.0, 0.0), |(res, tot), (x, y)| {
+            (
+                res + (y - self.eval(&coefficients, *x)).powi(2),
+                tot + (y - mean).powi(2),
+            )
+        });
+        //constant data has no variance to explain, so r2 is 1 only for a fit that matches it
+        let tiny = 1e-12 * pts.iter().map(|(_, y)| y * y).sum::<f64>();
+        let r2 = if tot > tiny {
+            1.0 - res / tot
+        } else if res <= tiny {
+            1.0
+        } else {
+            0.0
+        };
+        Some(Fit { r2, coefficients })
+    }
+    fn name(&self) -> String {
+        match self {
+            FitModel::Linear => "linear".to_string(),
+            FitModel::Polynomial(n) => format!("degree {n}"),
+            FitModel::Exponential => "exponential".to_string(),
+            FitModel::Power => "power".to_string(),
+            FitModel::Custom(_, _) => "custom".to_string(),
+        }
+    }
+    ///the fitted function written out with its coefficients
+    fn formula(&self, c: &[f64]) -> String {
+        match self {
+            FitModel::Linear | FitModel::Polynomial(_) => {
+                let terms = c
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| match i {
+                        0 => format!("{c:E}"),
+                        1 => format!("{c:E}x"),
+                        _ => format!("{c:E}x^{i}"),
+                    })
+                    .collect::<Vec<String>>();
+                format!("y={}", terms.join("+"))
+            }
+            FitModel::Exponential => format!("y={:E}*exp({:E}x)", c[0], c[1]),
+            FitModel::Power => format!("y={:E}*x^{:E}", c[0], c[1]),
+            FitModel::Custom(_, _) => format!(
+                "p={}",
+                c.iter()
+                    .map(|c| format!("{c:E}"))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+impl Graph {
+    ///points of the kth series if it is Coord or Point data, using the real part
+    pub(crate) fn fit_points(&self, k: usize) -> Vec<(f64, f64)> {
+        fn push(data: &GraphType, pts: &mut Vec<(f64, f64)>) {
+            match data {
+                GraphType::Coord(data) => pts.extend(
+                    data.iter()
+                        .filter_map(|(x, z)| Some((*x, z.to_options().0?))),
+                ),
+                GraphType::Point(p) => pts.push((p.x, p.y)),
+                GraphType::List(a) => a.iter().for_each(|data| push(data, pts)),
+                _ => {}
+            }
+        }
+        let mut pts = Vec::new();
+        if let Some(data) = self.data.get(k) {
+            push(data, &mut pts)
+        }
+        pts.retain(|(x, y)| x.is_finite() && y.is_finite());
+        pts
+    }
+    ///sets the model fitted to the kth series, or removes its fit if none
+    pub fn set_fit(&mut self, k: usize, model: Option<FitModel>) {
+        self.fits.retain(|(n, _)| *n != k);
+        if let Some(model) = model {
+            self.fits.push((k, model))
+        }
+        self.fit_results = None;
+    }
+    ///results of every fit in the order of fits, none if the data could not be fitted
+    pub fn fit_results(&mut self) -> &[Option<Fit>] {
+        if self.fit_results.is_none() {
+            self.fit_results = Some(
+                self.fits
+                    .iter()
+                    .map(|(k, model)| model.fit(&self.fit_points(*k)))
+                    .collect(),
+            );
+        }
+        self.fit_results.as_ref().unwrap()
+    }
+    ///cycles the fit of the kth series through the built in models
+    pub(crate) fn cycle_fit(&mut self, k: usize) {
+        let next = match self.fits.iter().find(|(n, _)| *n == k).map(|(_, m)| m) {
+            None => Some(FitModel::Linear),
+            Some(FitModel::Linear) => Some(FitModel::Polynomial(2)),
+            Some(FitModel::Polynomial(2)) => Some(FitModel::Polynomial(3)),
+            Some(FitModel::Polynomial(3)) => Some(FitModel::Exponential),
+            Some(FitModel::Exponential) => Some(FitModel::Power),
+            Some(_) => None,
+        };
+        self.set_fit(k, next)
+    }
+    pub(crate) fn fit_name(&self, j: usize) -> String {
+        let (k, model) = &self.fits[j];
+        format!("{} fit {}", model.name(), self.series_name(*k))
+    }
+    ///lines describing each fit for the sidebar and clipboard
+    pub(crate) fn fit_list(&self) -> Vec<String> {
+        self.fits
+            .iter()
+            .enumerate()
+            .map(|(j, (_, model))| {
+                match self
+                    .fit_results
+                    .as_ref()
+                    .and_then(|r| r.get(j))
+                    .cloned()
+                    .flatten()
+                {
+                    Some(fit) => format!(
+                        "{} {} r²={}",
+                        self.fit_name(j),
+                        model.formula(&fit.coefficients),
+                        fit.r2
+                    ),
+                    None => format!("{} failed", self.fit_name(j)),
+                }
+            })
+            .collect()
+    }
+    ///the fitted curves sampled across the screen, and the residuals of each point if shown
+    pub(crate) fn fit_data(&self) -> Vec<(GraphType, Option<GraphType>)> {
+        let (a, _) = self.to_coord(Pos::new(0.0, 0.0));
+        let (b, _) = self.to_coord(Pos::new(self.screen.x as f32, 0.0));
+        let n = (self.screen.x * self.prec()) as usize + 1;
+        self.fits
+            .iter()
+            .enumerate()
+            .filter_map(|(j, (k, model))| {
+                let fit = self.fit_results.as_ref()?.get(j)?.as_ref()?;
+                let curve = (0..n.max(2))
+                    .map(|i| {
+                        let x = a + i as f64 / (n.max(2) - 1) as f64 * (b - a);
+                        Complex::Real(model.eval(&fit.coefficients, x))
+                    })
+                    .collect();
+                let residuals = self.show_residuals.then(|| {
+                    GraphType::List(
+                        self.fit_points(*k)
+                            .into_iter()
+                            .map(|(x, y)| {
+                                GraphType::Point(Vec2::new(x, y - model.eval(&fit.coefficients, x)))
+                            })
+                            .collect(),
+                    )
+                });
+                Some((GraphType::Width(curve, a, b), residuals))
+            })
+            .collect()
+    }
+}
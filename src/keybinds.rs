@@ -0,0 +1,513 @@
+use crate::types::*;
+use crate::ui::Painter;
+macro_rules! binds {
+    ($($(#[$m: meta])* $name: ident,)*) => {
+        impl Keybinds {
+            ///every action along with its name, in declaration order
+            pub fn binds(&self) -> Vec<(&'static str, Option<Keys>)> {
+                vec![$($(#[$m])* (stringify!($name), self.$name),)*]
+            }
+            pub(crate) fn binds_mut(&mut self) -> Vec<(&'static str, &mut Option<Keys>)> {
+                vec![$($(#[$m])* (stringify!($name), &mut self.$name),)*]
+            }
+        }
+    };
+}
+binds!(
+    left,
+    right,
+    up,
+    down,
+    left_3d,
+    right_3d,
+    up_3d,
+    down_3d,
+    in_3d,
+    out_3d,
+    zoom_in,
+    zoom_out,
+    zoom_in_x,
+    zoom_out_x,
+    zoom_in_y,
+    zoom_out_y,
+    zoom_in_z,
+    zoom_out_z,
+    lines,
+    axis,
+    coord,
+    anti_alias,
+    ignore_bounds,
+    color_depth,
+    zoom_in_3d,
+    zoom_out_3d,
+    show_box,
+    domain_alternate,
+    slice_up,
+    slice_down,
+    slice_view,
+    log_scale,
+    line_style,
+    var_up,
+    var_down,
+    var_in,
+    var_out,
+    prec_up,
+    prec_down,
+    ruler,
+    view,
+    mode_up,
+    mode_down,
+    reset,
+    side,
+    fast,
+    #[cfg(feature = "serde")]
+    save,
+    #[cfg(feature = "serde")]
+    share_data,
+    #[cfg(feature = "serde")]
+    full_save,
+    #[cfg(feature = "serde")]
+    paste,
+    settings,
+    rebind,
+    help,
+    #[cfg(feature = "serde")]
+    load,
+    #[cfg(any(feature = "skia", feature = "tiny-skia"))]
+    save_png,
+    only_real,
+    toggle_dark_mode,
+    contour,
+    zeros,
+    copy_zeros,
+    branch_cut,
+    conformal_polar,
+    zoom_previous,
+    view_back,
+    view_forward,
+    trace,
+    trace_series,
+    analysis,
+    analysis_next,
+    analysis_prev,
+    derivative,
+    integral,
+    fit,
+    residuals,
+    copy_fit,
+    export_data,
+    export_visible,
+);
+impl Keybinds {
+    ///pairs of actions which are bound to the same keys,
+    ///or where one is bound to the first key of the others chord
+    pub fn conflicts(&self) -> Vec<(&'static str, &'static str)> {
+        let binds = self
+            .binds()
+            .into_iter()
+            .filter_map(|(n, k)| k.map(|k| (n, k.normalize())))
+            .collect::<Vec<(&'static str, Keys)>>();
+        let mut conflicts = Vec::new();
+        for (i, (a, ka)) in binds.iter().enumerate() {
+            for (b, kb) in &binds[i + 1..] {
+                let shadows = |x: &Keys, y: &Keys| {
+                    y.prefix.is_none() && y.modifiers.is_none() && x.prefix == Some(y.key)
+                };
+                if ka == kb || shadows(ka, kb) || shadows(kb, ka) {
+                    conflicts.push((*a, *b))
+                }
+            }
+        }
+        conflicts
+    }
+}
+#[cfg(feature = "serde")]
+impl Keybinds {
+    ///human editable json of every keybind, where unbound actions are null,
+    ///keys are written like "ctrl+shift+A" and chords like "G Z"
+    pub fn to_config(&self) -> String {
+        let map = self
+            .binds()
+            .into_iter()
+            .map(|(n, k)| (n.to_string(), k.map(|k| k.to_string()).into()))
+            .collect::<serde_json::Map<String, serde_json::Value>>();
+        serde_json::to_string_pretty(&map).unwrap()
+    }
+    ///parses keybinds in the format of to_config, actions which are missing keep their default
+    pub fn from_config(s: &str) -> Result<Self, String> {
+        let map: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(s).map_err(|e| e.to_string())?;
+        let mut keybinds = Self::default();
+        let mut binds = keybinds.binds_mut();
+        for (name, v) in map {
+            let Some((_, b)) = binds.iter_mut().find(|(n, _)| *n == name) else {
+                return Err(format!("unknown action {name}"));
+            };
+            **b = match v {
+                serde_json::Value::Null => None,
+                serde_json::Value::String(s) => Some(s.parse()?),
+                _ => return Err(format!("expected a string or null for {name}")),
+            }
+        }
+        drop(binds);
+        Ok(keybinds)
+    }
+}
+impl Keys {
+    fn normalize(mut self) -> Self {
+        self.modifiers = self.modifiers.filter(|m| !m.is_false());
+        self
+    }
+}
+impl std::fmt::Display for Keys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(prefix) = self.prefix {
+            write!(f, "{prefix:?} ")?
+        }
+        if let Some(m) = self.modifiers {
+            for (b, n) in [
+                (m.ctrl, "ctrl"),
+                (m.alt, "alt"),
+                (m.shift, "shift"),
+                (m.mac_cmd, "mac_cmd"),
+                (m.command, "command"),
+            ] {
+                if b {
+                    write!(f, "{n}+")?
+                }
+            }
+        }
+        write!(f, "{:?}", self.key)
+    }
+}
+#[cfg(feature = "serde")]
+impl std::str::FromStr for Keys {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = |s: &str| {
+            serde_json::from_value::<Key>(serde_json::Value::String(s.to_string()))
+                .map_err(|_| format!("unknown key {s}"))
+        };
+        let (prefix, s) = match s.trim().split_once(' ') {
+            Some((p, s)) => (Some(key(p)?), s.trim()),
+            None => (None, s.trim()),
+        };
+        let mut modifiers = Modifiers::default();
+        let mut parts = s.split('+').collect::<Vec<&str>>();
+        let k = key(parts.pop().unwrap_or_default())?;
+        for m in parts {
+            match m {
+                "ctrl" => modifiers.ctrl = true,
+                "alt" => modifiers.alt = true,
+                "shift" => modifiers.shift = true,
+                "mac_cmd" => modifiers.mac_cmd = true,
+                "command" => modifiers.command = true,
+                _ => return Err(format!("unknown modifier {m}")),
+            }
+        }
+        Ok(Self {
+            key: k,
+            modifiers: (!modifiers.is_false()).then_some(modifiers),
+            prefix,
+        })
+    }
+}
+impl Graph {
+    #[cfg(feature = "serde")]
+    ///loads keybinds from Graph.keybinds_file, returning any conflicts between them
+    pub fn load_keybinds(&mut self) -> Result<Vec<(&'static str, &'static str)>, String> {
+        let s = std::fs::read_to_string(&self.keybinds_file).map_err(|e| e.to_string())?;
+        let keybinds = Keybinds::from_config(&s)?;
+        self.keybinds = Some(keybinds);
+        Ok(keybinds.conflicts())
+    }
+    #[cfg(feature = "serde")]
+    ///saves keybinds to Graph.keybinds_file
+    pub fn save_keybinds(&self) -> std::io::Result<()> {
+        self.write_keybinds(self.keybinds.as_ref().unwrap())
+    }
+    #[cfg(feature = "serde")]
+    pub(crate) fn write_keybinds(&self, keybinds: &Keybinds) -> std::io::Result<()> {
+        if let Some(parent) = std::path::Path::new(&self.keybinds_file).parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?
+        }
+        std::fs::write(&self.keybinds_file, keybinds.to_config())
+    }
+    ///lines shown in the rebinding menu, formatted as name=keys
+    pub(crate) fn keybind_list(&self, keybinds: &Keybinds) -> Vec<String> {
+        let conflicts = keybinds.conflicts();
+        keybinds
+            .binds()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, k))| {
+                let k = match self.rebind {
+                    Some((j, false, _)) if i == j => "press a key".to_string(),
+                    Some((j, true, None)) if i == j => "press 2 keys".to_string(),
+                    Some((j, true, Some(p))) if i == j => format!("{p:?} ..."),
+                    _ => k.map(|k| k.to_string()).unwrap_or_default(),
+                };
+                let c = conflicts
+                    .iter()
+                    .filter_map(|(a, b)| {
+                        if *a == name {
+                            Some(*b)
+                        } else if *b == name {
+                            Some(*a)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect::<Vec<&str>>();
+                if c.is_empty() {
+                    format!("{name}={k}")
+                } else {
+                    format!("{name}={k} conflicts with {}", c.join(","))
+                }
+            })
+            .collect()
+    }
+    ///handles input in the rebinding menu, returns true if the keys pressed were captured
+    pub(crate) fn rebind_keys(&mut self, i: &InputState, keybinds: &mut Keybinds) -> bool {
+        let Some(mut text_box) = self.text_box else {
+            return false;
+        };
+        if let Some((row, chord, prefix)) = self.rebind {
+            let Some(key) = i.keys_pressed.first().copied() else {
+                return false;
+            };
+            if chord && prefix.is_none() {
+                self.rebind = Some((row, chord, Some(key)));
+            } else {
+                *keybinds.binds_mut()[row].1 = Some(Keys {
+                    key,
+                    modifiers: (!chord && !i.modifiers.is_false()).then_some(i.modifiers),
+                    prefix,
+                });
+                self.rebind = None;
+            }
+            self.menu_lines = Some(self.keybind_list(keybinds));
+            return true;
+        }
+        let len = keybinds.binds().len();
+        for key in &i.keys_pressed {
+            match key {
+                Key::Enter => self.rebind = Some((text_box.1, i.modifiers.shift, None)),
+                Key::Delete | Key::Backspace => *keybinds.binds_mut()[text_box.1].1 = None,
+                Key::ArrowUp => text_box.1 = text_box.1.saturating_sub(1),
+                Key::ArrowDown => text_box.1 = (text_box.1 + 1).min(len - 1),
+                _ => continue,
+            }
+            self.menu_lines = Some(self.keybind_list(keybinds));
+        }
+        self.text_box = Some(text_box);
+        false
+    }
+    ///resolves chords into the keybinds active this frame,
+    ///while a chord is pending only its second keys are active
+    pub(crate) fn chord_keybinds(&mut self, i: &InputState, keybinds: &Keybinds) -> Keybinds {
+        let mut active = *keybinds;
+        let pending = self.chord;
+        if pending.is_some() && !i.keys_pressed.is_empty() {
+            self.chord = None
+        }
+        if pending.is_none()
+            && i.modifiers.is_false()
+            && let Some(key) = i.keys_pressed.iter().find(|k| {
+                keybinds
+                    .binds()
+                    .iter()
+                    .any(|(_, b)| b.and_then(|b| b.prefix) == Some(**k))
+            })
+        {
+            self.chord = Some(*key);
+            for (_, b) in active.binds_mut() {
+                *b = None
+            }
+            return active;
+        }
+        for (_, b) in active.binds_mut() {
+            if let Some(k) = b {
+                if k.prefix.is_some() && k.prefix == pending {
+                    k.prefix = None
+                } else if k.prefix.is_some() || pending.is_some() {
+                    *b = None
+                }
+            }
+        }
+        active
+    }
+}
+impl Graph {
+    ///group and short description of an action, None if it does nothing in the current mode
+    fn help_entry(&self, name: &str) -> Option<(&'static str, &'static str)> {
+        let (d2, d3) = (!self.is_3d, self.is_3d);
+        let dc = self.graph_mode == GraphMode::DomainColoring;
+        let var = matches!(self.graph_mode, GraphMode::Flatten | GraphMode::Depth);
+        let slice = self.is_3d_data
+            && matches!(
+                self.graph_mode,
+                GraphMode::Slice
+                    | GraphMode::Flatten
+                    | GraphMode::Depth
+                    | GraphMode::Polar
+                    | GraphMode::SlicePolar
+            );
+        let (group, desc, show) = match name {
+            "left" if self.trace.is_some() => ("general", "previous traced sample", true),
+            "right" if self.trace.is_some() => ("general", "next traced sample", true),
+            "up" if self.trace.is_some() => ("general", "next traced row", true),
+            "down" if self.trace.is_some() => ("general", "previous traced row", true),
+            "left" if d3 => ("3d", "rotate left", true),
+            "right" if d3 => ("3d", "rotate right", true),
+            "up" if d3 => ("3d", "rotate up", true),
+            "down" if d3 => ("3d", "rotate down", true),
+            "left" => ("2d", "move left", true),
+            "right" => ("2d", "move right", true),
+            "up" => ("2d", "move up", true),
+            "down" => ("2d", "move down", true),
+            "left_3d" => ("3d", "move view left", d3),
+            "right_3d" => ("3d", "move view right", d3),
+            "up_3d" => ("3d", "move view up", d3),
+            "down_3d" => ("3d", "move view down", d3),
+            "in_3d" => ("3d", "move up the z axis", d3),
+            "out_3d" => ("3d", "move down the z axis", d3),
+            "zoom_in" => ("general", "zoom in", true),
+            "zoom_out" => ("general", "zoom out", true),
+            "zoom_in_x" => ("general", "zoom in x", true),
+            "zoom_out_x" => ("general", "zoom out x", true),
+            "zoom_in_y" => ("general", "zoom in y", true),
+            "zoom_out_y" => ("general", "zoom out y", true),
+            "zoom_in_z" => ("3d", "zoom in z", d3),
+            "zoom_out_z" => ("3d", "zoom out z", d3),
+            "lines" => ("general", "toggle grid lines", true),
+            "axis" => ("general", "toggle axis numbers", true),
+            "coord" => ("general", "toggle coordinates", true),
+            "anti_alias" => ("general", "toggle anti alias", true),
+            "ignore_bounds" => ("3d", "toggle ignoring box bounds", d3),
+            "color_depth" => ("3d", "cycle depth coloring", d3),
+            "zoom_in_3d" => ("3d", "shrink box", d3),
+            "zoom_out_3d" => ("3d", "grow box", d3),
+            "show_box" => ("3d", "toggle full box", d3),
+            "domain_alternate" => ("complex", "toggle alternate coloring", d2 && dc),
+            "slice_up" => ("complex", "next slice", slice),
+            "slice_down" => ("complex", "previous slice", slice),
+            "slice_view" => ("complex", "toggle slice axis", slice),
+            "log_scale" => ("complex", "toggle log scale", dc),
+            "line_style" => ("general", "cycle line style", true),
+            "var_up" => ("complex", "move input range up", var),
+            "var_down" => ("complex", "move input range down", var),
+            "var_in" => ("complex", "shrink input range", var),
+            "var_out" => ("complex", "grow input range", var),
+            "prec_up" => ("general", "increase precision", true),
+            "prec_down" => ("general", "decrease precision", true),
+            "ruler" => ("2d", "toggle ruler", d2),
+            "view" => ("complex", "cycle real/imag", self.is_complex),
+            "mode_up" => ("general", "next graph mode", true),
+            "mode_down" => ("general", "previous graph mode", true),
+            "reset" => ("general", "reset view", true),
+            "side" => ("sidebar", "toggle side menu", true),
+            "fast" => ("general", "toggle fast drawing", true),
+            "save" => ("sidebar", "copy tiny save", true),
+            "share_data" => ("sidebar", "copy tiny save with data", true),
+            "full_save" => ("sidebar", "full save", true),
+            "paste" => ("sidebar", "paste tiny save", true),
+            "settings" => ("sidebar", "toggle settings menu", true),
+            "rebind" => ("sidebar", "toggle rebinding menu", true),
+            "help" => ("general", "toggle this help", true),
+            "load" => ("sidebar", "toggle load menu", true),
+            "save_png" => ("general", "copy image", true),
+            "only_real" => ("complex", "toggle only real", self.is_complex),
+            "toggle_dark_mode" => ("general", "toggle dark mode", true),
+            "contour" => ("complex", "toggle contours", dc),
+            "zeros" => ("complex", "toggle zeros and poles", dc),
+            "copy_zeros" => ("complex", "copy zeros and poles", dc && self.show_zeros),
+            "branch_cut" => ("complex", "toggle branch cuts", dc || d3),
+            "conformal_polar" => (
+                "complex",
+                "toggle polar grid",
+                self.graph_mode == GraphMode::Conformal,
+            ),
+            "zoom_previous" => ("general", "undo box zoom", !self.zoom_history.is_empty()),
+            "view_back" => (
+                "general",
+                "previous view",
+                self.view_pos + 1 < self.view_history.len(),
+            ),
+            "view_forward" => ("general", "next view", self.view_pos != 0),
+            "trace" => ("general", "toggle trace mode", true),
+            "trace_series" => ("general", "next traced series", self.trace.is_some()),
+            "analysis" => ("2d", "toggle zeros, extrema and intersections", d2),
+            "analysis_next" => (
+                "2d",
+                "next zero, extremum or intersection",
+                d2 && self.show_analysis,
+            ),
+            "analysis_prev" => (
+                "2d",
+                "previous zero, extremum or intersection",
+                d2 && self.show_analysis,
+            ),
+            "derivative" => ("2d", "toggle derivative curve", d2),
+            "integral" => ("2d", "toggle integral curve", d2),
+            "fit" => ("2d", "cycle curve fit", d2),
+            "residuals" => ("2d", "toggle fit residuals", d2 && !self.fits.is_empty()),
+            "export_data" => ("general", "export data", true),
+            "export_visible" => ("general", "export visible data", true),
+            "copy_fit" => ("2d", "copy fit results", d2 && !self.fits.is_empty()),
+            _ => return None,
+        };
+        show.then_some((group, desc))
+    }
+    ///lines of the help overlay, grouped
+    pub(crate) fn help_list(&self) -> Vec<String> {
+        let keybinds = self.keybinds.as_ref().unwrap();
+        let mut lines = Vec::new();
+        for g in ["general", "2d", "3d", "complex", "sidebar"] {
+            let entries = keybinds
+                .binds()
+                .into_iter()
+                .filter_map(|(name, k)| {
+                    let k = k?;
+                    let (group, desc) = self.help_entry(name)?;
+                    (group == g).then(|| format!("{k} {desc}"))
+                })
+                .collect::<Vec<String>>();
+            if !entries.is_empty() {
+                lines.push(format!("{g}:"));
+                lines.extend(entries);
+            }
+        }
+        lines
+    }
+    pub(crate) fn write_help(&self, painter: &mut Painter) {
+        let lines = self.help_list();
+        let h = self.font_size;
+        let rows = (((self.screen.y as f32 - 2.0 * h) / h).floor() as usize).max(1);
+        let width = lines
+            .iter()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or_default() as f32
+            * self.font_width
+            + h;
+        let cols = lines.len().div_ceil(rows);
+        let rows = lines.len().div_ceil(cols.max(1));
+        painter.highlight(
+            h * 0.5,
+            h * 0.5,
+            h * 1.5 + width * cols as f32,
+            h * 1.5 + h * rows as f32,
+            &self.background_color,
+        );
+        for (i, l) in lines.iter().enumerate() {
+            let pos = Pos::new(
+                h + width * (i / rows) as f32,
+                h + h * (i % rows) as f32 + h * 0.5,
+            );
+            self.text(pos, Align::LeftCenter, l, &self.text_color, painter);
+        }
+    }
+}
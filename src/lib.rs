@@ -1,20 +1,36 @@
+mod analysis;
+mod csv;
+mod export;
+mod fit;
+mod keybinds;
+#[cfg(feature = "serde")]
+mod migrate;
+mod settings;
+#[cfg(feature = "serde")]
+mod share;
 mod sidebar;
 #[cfg(feature = "skia-vulkan")]
 pub mod skia_vulkan;
 pub mod types;
 mod ui;
+#[cfg(feature = "serde")]
+use crate::migrate::*;
 use crate::types::*;
 use crate::ui::Painter;
 #[cfg(feature = "serde")]
 use base64::Engine;
 #[cfg(feature = "rayon")]
 use rayon::slice::ParallelSliceMut;
-use std::f64::consts::{PI, TAU};
-#[cfg(feature = "serde")]
-use std::io::BufRead;
+use std::f64::consts::{FRAC_PI_4, PI, TAU};
 fn is_3d(data: &[GraphType]) -> bool {
-    data.iter()
-        .any(|c| matches!(c, GraphType::Width3D(_, _, _, _, _) | GraphType::Coord3D(_)))
+    data.iter().any(|c| {
+        matches!(
+            c,
+            GraphType::Width3D(_, _, _, _, _)
+                | GraphType::Coord3D(_)
+                | GraphType::Surface(_, _, _, _, _, _)
+        )
+    })
 }
 #[cfg(target_arch = "wasm32")]
 pub use ui::dpr;
@@ -93,21 +109,32 @@ impl Graph {
         self.side_drag.is_some() || self.side_slider.is_some()
     }
     #[cfg(any(feature = "skia", feature = "tiny-skia-text"))]
-    ///sets font
+    ///sets font, showing an error and keeping the current font if it can not be read
     pub fn set_font(&mut self, bytes: &[u8]) {
+        if let Err(e) = self.try_set_font(bytes) {
+            self.error = Some(e)
+        }
+    }
+    #[cfg(any(feature = "skia", feature = "tiny-skia-text"))]
+    ///sets font, ttf/otf for skia and bdf for tiny-skia
+    pub fn try_set_font(&mut self, bytes: &[u8]) -> Result<(), RuplError> {
         #[cfg(feature = "skia")]
         {
             let typeface = skia_safe::FontMgr::default()
                 .new_from_data(bytes, None)
-                .unwrap();
+                .ok_or_else(|| RuplError::Font("unsupported font data".to_string()))?;
             self.font = Some(skia_safe::Font::new(typeface, self.font_size));
         }
         #[cfg(feature = "tiny-skia-text")]
         {
-            self.font = bdf2::read(bytes).ok();
+            self.font = Some(
+                bdf2::read(bytes)
+                    .map_err(|_| RuplError::Font("unsupported font data".to_string()))?,
+            );
             self.font_cache = build_cache(&self.font, self.text_color);
         }
         self.font_width = 0.0;
+        Ok(())
     }
     ///sets the font color
     pub fn set_text_color(&mut self, color: Color) {
@@ -121,7 +148,11 @@ impl Graph {
     pub fn set_is_complex(&mut self, new: bool) {
         self.is_complex = new;
         match self.graph_mode {
-            GraphMode::Depth | GraphMode::DomainColoring | GraphMode::Flatten => {
+            GraphMode::Depth
+            | GraphMode::DomainColoring
+            | GraphMode::RiemannSphere
+            | GraphMode::Conformal
+            | GraphMode::Flatten => {
                 self.graph_mode = GraphMode::Normal;
                 self.is_3d = self.is_3d_data;
                 self.recalculate(None);
@@ -151,6 +182,9 @@ impl Graph {
     ///removes data in nth slot
     pub fn remove_data(&mut self, n: usize) {
         self.data.remove(n);
+        self.analysis = None;
+        self.fit_results = None;
+        self.seams = None;
     }
     ///insert and replace data into nth slot
     pub fn insert_data(&mut self, data: GraphType, n: usize) {
@@ -159,12 +193,39 @@ impl Graph {
         } else {
             self.data[n] = data
         }
+        #[cfg(feature = "serde")]
+        self.keep_shared();
+        self.analysis = None;
+        self.fit_results = None;
+        self.seams = None;
     }
     ///sets data and resets domain coloring cache
     pub fn set_data(&mut self, data: Vec<GraphType>) {
         self.data = data;
+        #[cfg(feature = "serde")]
+        self.keep_shared();
+        self.cache = None;
+        self.analysis = None;
+        self.fit_results = None;
+        self.seams = None;
+    }
+    ///sets the heatmap colormap and resets cache
+    pub fn set_colormap(&mut self, colormap: Colormap) {
+        self.colormap = colormap;
+        self.cache = None;
+    }
+    ///sets a fixed heatmap min/max, None to take it from the data, and resets cache
+    pub fn set_heatmap_range(&mut self, range: Option<Vec2>) {
+        self.heatmap_range = range;
         self.cache = None;
     }
+    ///sets modulus spacing, log spacing and argument divisions of contour lines
+    pub fn set_contour(&mut self, abs: Option<f64>, log: bool, arg: Option<usize>) {
+        self.contour_abs = abs;
+        self.contour_log = log;
+        self.contour_arg = arg;
+        self.contours = None;
+    }
     pub(crate) fn reset_offset(&self, width: f64, height: f64) -> Vec2 {
         let (_, _, screen) = self.get_new_screen(width, height, true);
         let s = screen - self.screen;
@@ -219,7 +280,7 @@ impl Graph {
             self.screen.y * 0.5,
         );
         if t != self.screen_offset && offset {
-            if self.graph_mode == GraphMode::DomainColoring {
+            if self.is_image() {
                 self.recalculate(None);
             }
             self.screen_offset = t;
@@ -232,6 +293,9 @@ impl Graph {
     pub fn clear_data(&mut self) {
         self.data.clear();
         self.cache = None;
+        self.analysis = None;
+        self.fit_results = None;
+        self.seams = None;
     }
     ///resets current 3d view based on the data that is supplied
     pub fn reset_3d(&mut self) {
@@ -249,7 +313,14 @@ impl Graph {
         self.is_3d_data = new;
         match self.graph_mode {
             GraphMode::Normal | GraphMode::Flatten | GraphMode::Polar => self.is_3d = new,
-            GraphMode::Slice | GraphMode::DomainColoring | GraphMode::SlicePolar if !new => {
+            GraphMode::Slice
+            | GraphMode::DomainColoring
+            | GraphMode::Heatmap
+            | GraphMode::RiemannSphere
+            | GraphMode::Conformal
+            | GraphMode::SlicePolar
+                if !new =>
+            {
                 self.graph_mode = GraphMode::Normal
             }
             GraphMode::Depth => {}
@@ -263,10 +334,12 @@ impl Graph {
     pub fn set_mode(&mut self, mode: GraphMode) {
         match mode {
             GraphMode::DomainColoring
+            | GraphMode::Heatmap
+            | GraphMode::Conformal
             | GraphMode::Slice
             | GraphMode::Flatten
             | GraphMode::SlicePolar => self.is_3d = false,
-            GraphMode::Depth => self.is_3d = true,
+            GraphMode::Depth | GraphMode::RiemannSphere => self.is_3d = true,
             _ => {
                 self.is_3d = self.is_3d_data;
             }
@@ -305,6 +378,10 @@ impl Graph {
         self.recalculate = true;
     }
     pub(crate) fn name_modified(&mut self, name: Option<usize>) {
+        if matches!(self.menu, Menu::Settings) {
+            self.apply_settings();
+            return;
+        }
         if let Some(n) = self.name_updated.as_mut() {
             *n = usize::MAX
         } else {
@@ -341,18 +418,29 @@ impl Graph {
                             self.bound.y / self.zoom_3d.y - self.offset3d.y,
                             Prec::Mult(self.prec),
                         ),
-                        GraphMode::DomainColoring => {
+                        GraphMode::RiemannSphere => {
+                            Bound::Width3D(-1.0, -1.0, 1.0, 1.0, Prec::Sphere(self.prec))
+                        }
+                        GraphMode::Conformal => Bound::Width3D(
+                            self.var.x,
+                            self.var.x,
+                            self.var.y,
+                            self.var.y,
+                            Prec::Mult(self.prec),
+                        ),
+                        GraphMode::DomainColoring | GraphMode::Heatmap => {
                             let c = self.to_coord(Pos::new(0.0, 0.0));
                             let cf = self.to_coord(self.screen.to_pos());
+                            self.image_dims = (
+                                (self.screen.x * prec * self.mult) as usize,
+                                (self.screen.y * prec * self.mult) as usize,
+                            );
                             Bound::Width3D(
                                 c.0,
                                 c.1,
                                 cf.0,
                                 cf.1,
-                                Prec::Dimension(
-                                    (self.screen.x * prec * self.mult) as usize,
-                                    (self.screen.y * prec * self.mult) as usize,
-                                ),
+                                Prec::Dimension(self.image_dims.0, self.image_dims.1),
                             )
                         }
                         GraphMode::Slice => {
@@ -633,17 +721,34 @@ impl Graph {
             self.screen.x
         } / (self.bound.y - self.bound.x);
         if !self.is_3d {
-            if self.graph_mode == GraphMode::DomainColoring {
+            if self.is_image() {
                 plot(painter, self);
                 self.write_axis(painter);
+                if self.graph_mode == GraphMode::Heatmap {
+                    self.write_colorbar(painter);
+                }
+                if self.graph_mode == GraphMode::DomainColoring && self.show_contour {
+                    self.write_contour(painter);
+                }
+                if self.graph_mode == GraphMode::DomainColoring && self.show_branch {
+                    self.write_branch(painter);
+                }
+                if self.graph_mode == GraphMode::DomainColoring && self.show_zeros {
+                    self.write_zeros(painter);
+                }
             } else if self.is_polar() {
                 self.write_polar_axis(painter);
                 plot(painter, self);
+            } else if self.graph_mode == GraphMode::Conformal {
+                self.write_conformal_axis(painter);
+                plot(painter, self);
             } else {
                 self.write_axis(painter);
                 plot(painter, self);
             }
-            self.write_text(painter);
+            if self.graph_mode != GraphMode::Conformal {
+                self.write_text(painter);
+            }
         } else {
             (self.sin_phi, self.cos_phi) = self.angle.x.sin_cos();
             (self.sin_theta, self.cos_theta) = self.angle.y.sin_cos();
@@ -668,11 +773,21 @@ impl Graph {
         }
         let draw = !matches!(self.menu, Menu::Normal);
         if !self.is_3d {
+            if self.show_analysis && self.graph_mode == GraphMode::Normal {
+                self.write_analysis(painter);
+            }
             self.write_coord(painter);
         } else {
             self.write_angle(painter);
         }
         self.write_label(painter);
+        self.write_trace(painter);
+        self.write_hover(painter);
+        self.write_zoom_box(painter);
+        self.write_error(painter);
+        if self.show_help {
+            self.write_help(painter);
+        }
         if draw {
             self.set_screen(width, height, false, false);
             if painter.offset.x == painter.offset.y && painter.offset.x == 0.0 {
@@ -686,29 +801,35 @@ impl Graph {
     }
     fn write_label(&self, painter: &mut Painter) {
         let mut pos = Pos::new(self.screen.x as f32 - 48.0, 0.0);
-        let blacklist = self
-            .blacklist_graphs
-            .iter()
-            .filter_map(|i| self.index_to_name(*i, true).0)
-            .collect::<Vec<usize>>();
-        for (i, Name { name, show, .. }) in self
-            .names
-            .iter()
-            .enumerate()
-            .filter_map(|(i, n)| {
-                if !n.name.is_empty() && !blacklist.contains(&i) {
-                    Some(n)
-                } else {
-                    None
-                }
-            })
+        let labels = self
+            .series_list()
+            .into_iter()
+            .map(|n| (n.name.clone(), n.show))
             .enumerate()
-        {
+            .map(|(i, (name, show))| (i, name, show))
+            .chain(self.companions.iter().enumerate().map(|(j, (k, c))| {
+                (
+                    self.data.len() + j,
+                    self.companion_name(*k, *c),
+                    self.series(*k).map(|n| n.show).unwrap_or_default(),
+                )
+            }))
+            .chain((0..self.fits.len()).filter(|_| !self.is_3d).map(|j| {
+                (
+                    self.data.len() + self.companions.len() + j,
+                    self.fit_name(j),
+                    self.series(self.fits[j].0)
+                        .map(|n| n.show)
+                        .unwrap_or_default(),
+                )
+            }))
+            .collect::<Vec<(usize, String, Show)>>();
+        for (i, name, show) in &labels {
             let y = (pos.y + 3.0 * self.font_size / 4.0).round();
             let o = 3.5;
             match self.graph_mode {
-                GraphMode::DomainColoring => {}
-                GraphMode::Flatten | GraphMode::Depth => {
+                GraphMode::DomainColoring | GraphMode::Heatmap | GraphMode::RiemannSphere => {}
+                GraphMode::Flatten | GraphMode::Depth | GraphMode::Conformal => {
                     self.text_color(pos, Align::RightTop, name, painter);
                     painter.line_segment(
                         [
@@ -772,12 +893,378 @@ impl Graph {
             pos.y += self.font_size;
         }
     }
+    fn write_contour(&self, painter: &mut Painter) {
+        if let Some(contours) = &self.contours {
+            for (a, b) in contours {
+                painter.line_segment(
+                    [self.to_screen(a.x, a.y), self.to_screen(b.x, b.y)],
+                    1.0,
+                    &self.axis_color,
+                );
+            }
+        }
+    }
+    fn write_branch(&self, painter: &mut Painter) {
+        if let Some(cuts) = &self.branch_cuts {
+            for (a, b) in cuts {
+                painter.line_segment(
+                    [self.to_screen(a.x, a.y), self.to_screen(b.x, b.y)],
+                    2.0 * self.line_width,
+                    &self.branch_color,
+                );
+            }
+        }
+    }
+    fn conformal_pos(&self, x: f64, y: f64, right: bool) -> Pos {
+        let p = self.to_screen(x, y);
+        let (w, h) = (self.screen.x as f32, self.screen.y as f32);
+        Pos::new(
+            p.x * 0.5 + if right { w * 0.5 } else { 0.0 },
+            p.y * 0.5 + h * 0.25,
+        )
+    }
+    fn conformal_inv(&self, p: Pos) -> Pos {
+        let (w, h) = (self.screen.x as f32, self.screen.y as f32);
+        let x = if p.x > w * 0.5 { p.x - w * 0.5 } else { p.x };
+        Pos::new(2.0 * x, 2.0 * (p.y - h * 0.25))
+    }
+    fn write_conformal_axis(&self, painter: &mut Painter) {
+        let (w, h) = (self.screen.x as f32, self.screen.y as f32);
+        painter.vline(w * 0.5, h, &self.axis_color);
+        if self.disable_axis {
+            return;
+        }
+        for (right, min, max) in [(false, 0.0, w * 0.5), (true, w * 0.5, w)] {
+            let o = self.conformal_pos(0.0, 0.0, right);
+            if (0.0..=h).contains(&o.y) {
+                painter.line_segment(
+                    [Pos::new(min, o.y), Pos::new(max, o.y)],
+                    1.0,
+                    &self.axis_color,
+                );
+            }
+            if (min..=max).contains(&o.x) {
+                painter.line_segment(
+                    [Pos::new(o.x, 0.0), Pos::new(o.x, h)],
+                    1.0,
+                    &self.axis_color,
+                );
+            }
+        }
+    }
+    ///grid size of 3d data, domain coloring and heatmaps are sampled at the screen resolution
+    fn grid_len(&self, n: usize) -> (usize, usize) {
+        if matches!(
+            self.graph_mode,
+            GraphMode::DomainColoring | GraphMode::Heatmap
+        ) && !self.is_3d
+        {
+            let lenx = (self.screen.x * self.prec() * self.mult) as usize;
+            let leny = (self.screen.y * self.prec() * self.mult) as usize;
+            if lenx * leny == n {
+                return (lenx, leny);
+            }
+        }
+        (n.isqrt(), n.isqrt())
+    }
+    fn trace_points(&self) -> Vec<(f64, Option<f64>, Complex)> {
+        self.trace
+            .map(|k| self.series_points(k))
+            .unwrap_or_default()
+    }
+    ///samples of the kth series, as the x input, the y input if the series is 3d, and the value
+    fn series_points(&self, k: usize) -> Vec<(f64, Option<f64>, Complex)> {
+        fn at(i: usize, n: usize, s: f64, e: f64) -> f64 {
+            if n > 1 {
+                s + i as f64 / (n - 1) as f64 * (e - s)
+            } else {
+                s
+            }
+        }
+        fn grid(
+            data: &[Complex],
+            (lenx, leny): (usize, usize),
+            (sx, sy, ex, ey): (f64, f64, f64, f64),
+            pts: &mut Vec<(f64, Option<f64>, Complex)>,
+        ) {
+            if lenx == 0 {
+                return;
+            }
+            pts.extend(data.iter().enumerate().map(|(i, z)| {
+                (
+                    at(i % lenx, lenx, sx, ex),
+                    Some(at(i / lenx, leny, sy, ey)),
+                    *z,
+                )
+            }))
+        }
+        fn push(g: &Graph, data: &GraphType, pts: &mut Vec<(f64, Option<f64>, Complex)>) {
+            match data {
+                GraphType::Width(data, s, e) => pts.extend(
+                    data.iter()
+                        .enumerate()
+                        .map(|(i, z)| (at(i, data.len(), *s, *e), None, *z)),
+                ),
+                GraphType::Coord(data) => pts.extend(data.iter().map(|(x, z)| (*x, None, *z))),
+                GraphType::Width3D(data, sx, sy, ex, ey) => {
+                    grid(data, g.grid_len(data.len()), (*sx, *sy, *ex, *ey), pts)
+                }
+                GraphType::Coord3D(data) => {
+                    pts.extend(data.iter().map(|(x, y, z)| (*x, Some(*y), *z)))
+                }
+                GraphType::Surface(sheets, sx, sy, ex, ey, _) => {
+                    for data in sheets {
+                        let len = data.len().isqrt();
+                        grid(data, (len, len), (*sx, *sy, *ex, *ey), pts)
+                    }
+                }
+                GraphType::List(a) => a.iter().for_each(|data| push(g, data, pts)),
+                GraphType::Constant(_, _) | GraphType::Point(_) | GraphType::None => {}
+            }
+        }
+        let mut pts = Vec::new();
+        if let Some(data) = self.data.get(k) {
+            push(self, data, &mut pts)
+        }
+        pts
+    }
+    ///real samples of the kth series if it is 2d, sorted by x
+    pub(crate) fn series_real(&self, k: usize) -> Vec<(f64, f64)> {
+        let mut pts = self
+            .series_points(k)
+            .into_iter()
+            .filter_map(|(x, y, z)| {
+                let v = z.to_options().0?;
+                (y.is_none() && x.is_finite() && v.is_finite()).then_some((x, v))
+            })
+            .collect::<Vec<(f64, f64)>>();
+        pts.sort_by(|p, q| p.0.total_cmp(&q.0));
+        pts
+    }
+    ///the real part of the kth series between a and b, clamped to its data,
+    ///with the ends linearly interpolated between samples
+    fn series_interval(&self, k: usize, a: f64, b: f64) -> Vec<(f64, f64)> {
+        let pts = self.series_real(k);
+        let (Some(first), Some(last)) = (pts.first(), pts.last()) else {
+            return Vec::new();
+        };
+        let (a, b) = (a.min(b).max(first.0), a.max(b).min(last.0));
+        if a >= b {
+            return Vec::new();
+        }
+        let mut curve = vec![(a, lerp(&pts, a))];
+        curve.extend(pts.iter().filter(|p| p.0 > a && p.0 < b));
+        curve.push((b, lerp(&pts, b)));
+        curve
+    }
+    fn write_ruler_area(&self, curve: &[(f64, f64)], k: usize, painter: &mut Painter) {
+        let color = &self.main_colors[k % self.main_colors.len()];
+        let (Some(first), Some(last)) = (curve.first(), curve.last()) else {
+            return;
+        };
+        let (sa, sb) = (self.to_screen(first.0, 0.0), self.to_screen(last.0, 0.0));
+        let mut i = 0;
+        let mut x = sa.x.max(0.0).ceil();
+        while x <= sb.x.min(self.screen.x as f32) {
+            let (cx, _) = self.to_coord(Pos::new(x, sa.y));
+            while i + 2 < curve.len() && curve[i + 1].0 < cx {
+                i += 1
+            }
+            let ((x0, y0), (x1, y1)) = (curve[i], curve[(i + 1).min(curve.len() - 1)]);
+            let y = if x1 == x0 {
+                y1
+            } else {
+                y0 + (y1 - y0) * (cx - x0) / (x1 - x0)
+            };
+            painter.line_segment(
+                [Pos::new(x, sa.y), Pos::new(x, self.to_screen(cx, y).y)],
+                1.0,
+                color,
+            );
+            x += 4.0;
+        }
+    }
+    ///where a sample is drawn on screen in the current mode, if it is drawn as a point
+    fn trace_pos(&self, x: f64, y: Option<f64>, z: Complex) -> Option<Pos> {
+        let (re, im) = z.to_options();
+        let v = re
+            .filter(|_| self.show.real())
+            .or(im.filter(|_| self.show.imag()));
+        let to_3d = |x: f64, y: f64, z: f64| {
+            let v = Vec3::new(
+                x - self.offset3d.x,
+                y + self.offset3d.y,
+                z + self.offset3d.z,
+            );
+            self.vec3_to_pos_depth(v, true).0
+        };
+        let p = match (self.graph_mode, y) {
+            (GraphMode::Normal, None) if !self.is_3d => self.to_screen(x, v?),
+            (GraphMode::Polar, None) if !self.is_3d => {
+                let (s, c) = x.sin_cos();
+                self.to_screen(c * v?, s * v?)
+            }
+            (GraphMode::Flatten, None) => self.to_screen(re?, im?),
+            (GraphMode::Depth, None) => to_3d(re?, im?, x),
+            (GraphMode::Normal, Some(y)) if self.is_3d => to_3d(x, y, v?),
+            (GraphMode::DomainColoring | GraphMode::Heatmap, Some(y)) => self.to_screen(x, y),
+            _ => return None,
+        };
+        (p.x.is_finite() && p.y.is_finite()).then_some(p)
+    }
+    ///the traced sample, stepped to by keys or else the nearest to the cursor
+    fn trace_current(&self, pts: &[(f64, Option<f64>, Complex)]) -> Option<usize> {
+        if let Some(i) = self.trace_index.filter(|i| *i < pts.len()) {
+            return Some(i);
+        }
+        let mpos = self.mouse_position?.to_pos();
+        pts.iter()
+            .enumerate()
+            .filter_map(|(i, (x, y, z))| {
+                let p = self.trace_pos(*x, *y, *z)?;
+                let (dx, dy) = (p.x - mpos.x, p.y - mpos.y);
+                Some((i, dx * dx + dy * dy))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+    ///how many samples apart neighbouring rows of the traced series are, 0 if it is 2d
+    fn trace_row(&self) -> usize {
+        match self.trace.and_then(|k| self.data.get(k)) {
+            Some(GraphType::Width3D(data, _, _, _, _)) => self.grid_len(data.len()).0,
+            Some(GraphType::Surface(data, _, _, _, _, _)) => {
+                data.first().map(|d| d.len().isqrt()).unwrap_or_default()
+            }
+            _ => 0,
+        }
+    }
+    fn trace_step(&mut self, step: isize) {
+        let pts = self.trace_points();
+        if pts.is_empty() {
+            return;
+        }
+        let i = self.trace_current(&pts).unwrap_or_default() as isize + step;
+        self.trace_index = Some(i.clamp(0, pts.len() as isize - 1) as usize);
+    }
+    fn write_trace(&self, painter: &mut Painter) {
+        if self.trace.is_none() {
+            return;
+        }
+        let pts = self.trace_points();
+        let Some(i) = self.trace_current(&pts) else {
+            return;
+        };
+        let (x, y, z) = pts[i];
+        if let Some(p) = self.trace_pos(x, y, z) {
+            painter.circle(
+                p,
+                2.0 * self.point_size,
+                &self.select_color,
+                self.line_width,
+            );
+        }
+        let (re, im) = z.to_options();
+        let (re, im) = (re.unwrap_or(0.0), im.unwrap_or(0.0));
+        let input = if let Some(y) = y {
+            format!("{x:E}\n{y:E}")
+        } else {
+            format!("{x:E}")
+        };
+        let s = format!(
+            "{input}\n{:E}\n{:E}\n{:E}\n{}",
+            re,
+            im,
+            im.hypot(re),
+            self.angle_type.to_val(im.atan2(re))
+        );
+        let (pos, align) = if self.is_3d {
+            (self.screen.to_pos(), Align::RightBottom)
+        } else {
+            (Pos::new(0.0, self.screen.y as f32), Align::LeftBottom)
+        };
+        self.text(pos, align, &s, &self.text_color, painter);
+    }
+    fn write_zeros(&self, painter: &mut Painter) {
+        if let Some(zeros) = &self.zeros {
+            let r = self.point_size;
+            for (p, n) in zeros {
+                let s = self.to_screen(p.x, p.y);
+                if *n > 0 {
+                    painter.circle(s, r, &self.text_color, self.line_width);
+                } else {
+                    painter.line_segment(
+                        [Pos::new(s.x - r, s.y - r), Pos::new(s.x + r, s.y + r)],
+                        self.line_width,
+                        &self.text_color,
+                    );
+                    painter.line_segment(
+                        [Pos::new(s.x - r, s.y + r), Pos::new(s.x + r, s.y - r)],
+                        self.line_width,
+                        &self.text_color,
+                    );
+                }
+                if n.abs() > 1 {
+                    self.text(
+                        Pos::new(s.x + r, s.y - r),
+                        Align::LeftBottom,
+                        &n.abs().to_string(),
+                        &self.text_color,
+                        painter,
+                    );
+                }
+            }
+        }
+    }
+    pub(crate) fn zeros_list(&self) -> Vec<String> {
+        self.zeros
+            .iter()
+            .flatten()
+            .map(|(p, n)| {
+                let name = if *n > 0 { "zero" } else { "pole" };
+                if n.abs() > 1 {
+                    format!("{name}({}) {:E},{:E}", n.abs(), p.x, p.y)
+                } else {
+                    format!("{name} {:E},{:E}", p.x, p.y)
+                }
+            })
+            .collect()
+    }
+    fn write_colorbar(&self, painter: &mut Painter) {
+        let h = (self.screen.y / 2.0) as f32;
+        let (x, y) = (self.screen.x as f32 - 24.0, (self.screen.y / 4.0) as f32);
+        for j in 0..h as usize {
+            let t = 1.0 - j as f64 / (h - 1.0) as f64;
+            painter.line_segment(
+                [Pos::new(x, y + j as f32), Pos::new(x + 16.0, y + j as f32)],
+                1.0,
+                &self.colormap.to_color(t),
+            );
+        }
+        self.text(
+            Pos::new(x - 4.0, y),
+            Align::RightCenter,
+            &format!("{:E}", self.heatmap_bound.y),
+            &self.text_color,
+            painter,
+        );
+        self.text(
+            Pos::new(x - 4.0, y + h),
+            Align::RightCenter,
+            &format!("{:E}", self.heatmap_bound.x),
+            &self.text_color,
+            painter,
+        );
+    }
     fn write_coord(&self, painter: &mut Painter) {
         if self.mouse_moved
             && let Some(pos) = self.mouse_position
         {
-            let p = self.to_coord(pos.to_pos());
-            if !self.disable_coord {
+            let p = if self.graph_mode == GraphMode::Conformal {
+                self.to_coord(self.conformal_inv(pos.to_pos()))
+            } else {
+                self.to_coord(pos.to_pos())
+            };
+            if !self.disable_coord && self.trace.is_none() {
                 let s = if self.graph_mode == GraphMode::DomainColoring {
                     if let GraphType::Width3D(data, sx, sy, ex, ey) = &self.data[0] {
                         let len = data.len().isqrt();
@@ -802,6 +1289,34 @@ impl Graph {
                     } else {
                         format!("{:E}\n{:E}", p.0, p.1)
                     }
+                } else if self.graph_mode == GraphMode::Heatmap {
+                    let screen = (
+                        (self.screen.x * self.prec() * self.mult) as usize,
+                        (self.screen.y * self.prec() * self.mult) as usize,
+                    );
+                    if let Some(GraphType::Width3D(data, sx, sy, ex, ey)) = self.data.first()
+                        && let Some((lenx, leny)) = [self.image_dims, screen]
+                            .into_iter()
+                            .find(|(x, y)| x * y == data.len())
+                        && lenx > 1
+                        && leny > 1
+                    {
+                        let i = ((p.0 - sx) / (ex - sx) * (lenx - 1) as f64).round();
+                        let j = ((p.1 - sy) / (ey - sy) * (leny - 1) as f64).round();
+                        if (0.0..lenx as f64).contains(&i) && (0.0..leny as f64).contains(&j) {
+                            let ind = i as usize + lenx * j as usize;
+                            format!(
+                                "{:E}\n{:E}\n{:E}",
+                                p.0,
+                                p.1,
+                                data[ind].to_options().0.unwrap_or(f64::NAN)
+                            )
+                        } else {
+                            format!("{:E}\n{:E}", p.0, p.1)
+                        }
+                    } else {
+                        format!("{:E}\n{:E}", p.0, p.1)
+                    }
                 } else if matches!(self.graph_mode, GraphMode::Polar | GraphMode::SlicePolar) {
                     format!(
                         "{:E}\n{}",
@@ -822,16 +1337,40 @@ impl Graph {
             if let Some(ps) = self.ruler_pos {
                 let dx = p.0 - ps.x;
                 let dy = p.1 - ps.y;
+                let mut s = format!(
+                    "{:E}\n{:E}\n{:E}\n{}",
+                    dx,
+                    dy,
+                    dy.hypot(dx),
+                    self.angle_type.to_val(dy.atan2(dx))
+                );
+                let k = self.trace.unwrap_or(0);
+                let curve = if self.graph_mode == GraphMode::Normal {
+                    self.series_interval(k, ps.x, p.0)
+                } else {
+                    Vec::new()
+                };
+                if let (Some(first), Some(last)) = (curve.first(), curve.last()) {
+                    self.write_ruler_area(&curve, k, painter);
+                    let (mut area, mut arc) = (0.0, 0.0);
+                    for w in curve.windows(2) {
+                        let ((x0, y0), (x1, y1)) = (w[0], w[1]);
+                        area += (x1 - x0) * (y0 + y1) * 0.5;
+                        arc += (y1 - y0).hypot(x1 - x0);
+                    }
+                    let w = last.0 - first.0;
+                    s += &format!(
+                        "\n{:E}\n{:E}\n{:E}\n{:E}",
+                        area,
+                        (last.1 - first.1) / w,
+                        arc,
+                        area / w
+                    );
+                }
                 self.text(
                     self.screen.to_pos(),
                     Align::RightBottom,
-                    &format!(
-                        "{:E}\n{:E}\n{:E}\n{}",
-                        dx,
-                        dy,
-                        dy.hypot(dx),
-                        self.angle_type.to_val(dy.atan2(dx))
-                    ),
+                    &s,
                     &self.text_color,
                     painter,
                 );
@@ -920,6 +1459,147 @@ impl Graph {
         let y = (oy - p.y as f64 / self.zoom.y) * s;
         (x, y)
     }
+    pub(crate) fn view(&self) -> View {
+        View {
+            offset: self.offset,
+            zoom: self.zoom,
+            offset3d: self.offset3d,
+            zoom_3d: self.zoom_3d,
+            angle: self.angle,
+            graph_mode: self.graph_mode,
+            slice: self.slice,
+            var: self.var,
+        }
+    }
+    pub(crate) fn set_view(&mut self, view: View) {
+        self.offset = view.offset;
+        self.zoom = view.zoom;
+        self.offset3d = view.offset3d;
+        self.zoom_3d = view.zoom_3d;
+        self.angle = view.angle;
+        self.slice = view.slice;
+        self.var = view.var;
+        self.set_mode(view.graph_mode);
+    }
+    ///adds the current view to the history if it changed,
+    ///consecutive small pans of the same kind, by dragging or by keys, replace the last entry
+    fn view_record(&mut self) {
+        const MAX_VIEWS: usize = 256;
+        if self.view_merge == Some(true) && !self.mouse_held {
+            self.view_merge = None
+        }
+        let now = self.view();
+        let Some(&last) = self
+            .view_history
+            .len()
+            .checked_sub(1 + self.view_pos)
+            .and_then(|i| self.view_history.get(i))
+        else {
+            return;
+        };
+        if now == last {
+            return;
+        }
+        self.view_history
+            .drain(self.view_history.len() - self.view_pos..);
+        self.view_pos = 0;
+        let w = self.bound.y - self.bound.x;
+        let turn = |a: f64, b: f64| {
+            let d = (a - b).rem_euclid(TAU);
+            d.min(TAU - d)
+        };
+        let small = now.zoom == last.zoom
+            && now.zoom_3d == last.zoom_3d
+            && now.graph_mode == last.graph_mode
+            && now.slice == last.slice
+            && now.var == last.var
+            && ((now.offset.x - last.offset.x) * now.zoom.x).abs() < self.screen.x / 2.0
+            && ((now.offset.y - last.offset.y) * now.zoom.y).abs() < self.screen.y / 2.0
+            && (now.offset3d.x - last.offset3d.x).abs() < w / 2.0
+            && (now.offset3d.y - last.offset3d.y).abs() < w / 2.0
+            && (now.offset3d.z - last.offset3d.z).abs() < w / 2.0
+            && turn(now.angle.x, last.angle.x) < FRAC_PI_4
+            && turn(now.angle.y, last.angle.y) < FRAC_PI_4;
+        if small && self.view_merge == Some(self.mouse_held) && self.view_history.len() > 1 {
+            *self.view_history.last_mut().unwrap() = now
+        } else {
+            self.view_history.push(now);
+            if self.view_history.len() > MAX_VIEWS {
+                self.view_history.remove(0);
+            }
+        }
+        self.view_merge = small.then_some(self.mouse_held)
+    }
+    ///sets the view so the screen rectangle between a and b fills the screen,
+    ///in 3d the rectangle is projected onto the plane through the middle of the box
+    ///and the x-y window is set to the region it covers
+    fn box_zoom(&mut self, a: Vec2, b: Vec2) {
+        let (l, r) = (a.x.min(b.x), a.x.max(b.x));
+        let (t, d) = (a.y.min(b.y), a.y.max(b.y));
+        if r - l < 4.0 || d - t < 4.0 {
+            return;
+        }
+        self.zoom_history.push(self.view());
+        if self.is_3d {
+            if self.sin_theta.abs() < 1e-3 {
+                self.zoom_history.pop();
+                return;
+            }
+            let s = self.delta / self.box_size;
+            let (mut x0, mut x1, mut y0, mut y1) = (f64::MAX, f64::MIN, f64::MAX, f64::MIN);
+            for (x, y) in [(l, t), (r, t), (l, d), (r, d)] {
+                let x1p = (x - self.screen.x * 0.5) / s;
+                let y1p = -(y - self.screen.y * 0.5) / (s * self.sin_theta);
+                let px = x1p * self.cos_phi - y1p * self.sin_phi;
+                let py = x1p * self.sin_phi + y1p * self.cos_phi;
+                let x = px / self.zoom_3d.x + self.offset3d.x;
+                let y = py / self.zoom_3d.y - self.offset3d.y;
+                x0 = x0.min(x);
+                x1 = x1.max(x);
+                y0 = y0.min(y);
+                y1 = y1.max(y);
+            }
+            let w = self.bound.y - self.bound.x;
+            self.zoom_3d.x = w / (x1 - x0);
+            self.zoom_3d.y = w / (y1 - y0);
+            self.offset3d.x = x0 - self.bound.x / self.zoom_3d.x;
+            self.offset3d.y = self.bound.x / self.zoom_3d.y - y0;
+        } else {
+            let (x0, y1) = self.to_coord(Pos::new(l as f32, t as f32));
+            let (x1, y0) = self.to_coord(Pos::new(r as f32, d as f32));
+            let s = self.screen.x / (self.bound.y - self.bound.x);
+            self.zoom = Vec2::new(
+                self.screen.x / ((x1 - x0) * s),
+                self.screen.y / ((y1 - y0) * s),
+            );
+            self.offset = Vec2::new(
+                -x0 * s - self.screen_offset.x,
+                y1 * s - self.screen_offset.y,
+            );
+        }
+        self.recalculate(None);
+    }
+    fn write_zoom_box(&self, painter: &mut Painter) {
+        if let (Some(a), Some(b)) = (self.zoom_box, self.mouse_position) {
+            let (a, b) = (a.to_pos(), b.to_pos());
+            let c = Pos::new(a.x, b.y);
+            let d = Pos::new(b.x, a.y);
+            for p in [[a, c], [c, b], [b, d], [d, a]] {
+                painter.line_segment(p, self.line_width, &self.select_color);
+            }
+        }
+    }
+    fn write_error(&self, painter: &mut Painter) {
+        if let Some(e) = &self.error {
+            self.text(
+                Pos::new(self.screen.x as f32 / 2.0, self.screen.y as f32),
+                Align::CenterBottom,
+                &e.to_string(),
+                &self.branch_color,
+                painter,
+            );
+        }
+    }
     fn get_new_offset(&self, mut o: Vec2) -> Vec2 {
         let s = (self.bound.y - self.bound.x) / self.screen.x;
         o /= s;
@@ -1054,7 +1734,7 @@ impl Graph {
         let s = self.screen.x / (self.bound.y - self.bound.x);
         let ox = self.screen_offset.x + self.offset.x;
         let oy = self.screen_offset.y + self.offset.y;
-        if !self.disable_lines && self.graph_mode != GraphMode::DomainColoring {
+        if !self.disable_lines && !self.is_image() {
             let nx = (((-1.0 / self.zoom.x - ox) / s) * 2.0 * minorx).ceil() as isize;
             let ny = (((oy + 1.0 / self.zoom.y) / s) * 2.0 * minory).ceil() as isize;
             let mx =
@@ -1191,6 +1871,12 @@ impl Graph {
     fn is_polar(&self) -> bool {
         matches!(self.graph_mode, GraphMode::Polar | GraphMode::SlicePolar)
     }
+    fn is_image(&self) -> bool {
+        matches!(
+            self.graph_mode,
+            GraphMode::DomainColoring | GraphMode::Heatmap
+        )
+    }
     #[cfg(feature = "tiny-skia-text")]
     fn font_width(&mut self) {
         if self.font_width == 0.0 {
@@ -1251,6 +1937,30 @@ impl Graph {
         )
     }
     #[allow(clippy::type_complexity)]
+    fn draw_seam(
+        &self,
+        a: Option<((Pos, Option<f32>), Vec3, bool)>,
+        b: Option<((Pos, Option<f32>), Vec3, bool)>,
+        buffer: &mut Option<Vec<(f32, Draw, Color)>>,
+        painter: &mut Painter,
+    ) {
+        if let (Some(a), Some(b)) = (a, b)
+            && a.2
+            && b.2
+        {
+            let d = (!self.fast_3d()).then(|| (a.0.1.unwrap() + b.0.1.unwrap()) * 0.5);
+            line(
+                buffer,
+                self.fast_3d().then_some(painter),
+                d,
+                a.0.0,
+                b.0.0,
+                self.branch_color,
+                2.0 * self.line_width,
+            );
+        }
+    }
+    #[allow(clippy::type_complexity)]
     #[allow(clippy::too_many_arguments)]
     fn draw_point_3d(
         &self,
@@ -1554,18 +2264,55 @@ impl Graph {
             crate::ui::write_clipboard(&s.0);
         }
     }
+    ///copies text to the clipboard, showing any error
+    pub(crate) fn copy_text(&mut self, text: &str) {
+        if let Some(clipboard) = self.clipboard.as_mut()
+            && let Err(e) = clipboard.set_text(text)
+        {
+            self.error = Some(e)
+        }
+    }
+    ///text on the clipboard, showing any error
+    pub(crate) fn paste_text(&mut self) -> String {
+        match self.clipboard.as_mut().map(|c| c.get_text()) {
+            Some(Ok(text)) => text,
+            Some(Err(e)) => {
+                self.error = Some(e);
+                String::new()
+            }
+            None => String::new(),
+        }
+    }
     fn keybinds_inner(&mut self, i: &InputState) {
+        if self.view_history.is_empty() {
+            self.view_history.push(self.view())
+        }
+        self.keybinds_input(i);
+        self.view_record();
+    }
+    fn keybinds_input(&mut self, i: &InputState) {
         #[cfg(any(feature = "skia", feature = "tiny-skia", feature = "wasm-draw"))]
         {
             self.request_redraw = false;
         }
-        let Some(keybinds) = std::mem::take(&mut self.keybinds) else {
+        let Some(mut binds) = std::mem::take(&mut self.keybinds) else {
             unreachable!()
         };
+        if matches!(self.menu, Menu::Keybinds) && self.rebind_keys(i, &mut binds) {
+            self.keybinds = Some(binds);
+            return;
+        }
+        if !i.keys_pressed.is_empty() {
+            self.error = None
+        }
+        let mut keybinds = self.chord_keybinds(i, &binds);
         #[cfg(feature = "arboard")]
         if self.clipboard.is_none() {
             if !self.wait_frame {
-                self.clipboard = Some(Clipboard(arboard::Clipboard::new().unwrap()))
+                match arboard::Clipboard::new() {
+                    Ok(clipboard) => self.clipboard = Some(Clipboard(clipboard)),
+                    Err(e) => self.error = Some(RuplError::Clipboard(e.to_string())),
+                }
             }
             self.wait_frame = false;
         }
@@ -1585,6 +2332,7 @@ impl Graph {
             } - self.draw_offset.to_vec();
             if let Some(pos) = self.mouse_position {
                 if mpos != pos {
+                    self.trace_index = None;
                     self.mouse_moved = true;
                     self.mouse_position = Some(mpos)
                 }
@@ -1756,28 +2504,132 @@ impl Graph {
                     self.text_box = None;
                     self.select = None;
                     self.side_drag = None;
+                    self.menu_lines = None;
+                    #[cfg(feature = "serde")]
+                    if self.save_num.is_some() {
+                        self.save()
+                    }
                 }
                 _ => {
                     self.menu = Menu::Settings;
+                    self.menu_lines = Some(self.settings_list());
+                    self.text_box = Some((0, 0));
+                    self.text_scroll_pos.0 = 0;
+                    self.select = None;
                     self.side_drag = None;
                 }
             }
         }
-        #[cfg(feature = "serde")]
-        if i.keys_pressed(keybinds.load) {
-            match self.menu {
-                Menu::Load => {
-                    self.menu = Menu::Normal;
-                    self.text_box = None;
+        if i.keys_pressed(keybinds.analysis) && !self.is_3d {
+            self.show_analysis = !self.show_analysis;
+            self.analysis = None;
+            self.analysis_index = None;
+        }
+        if self.show_analysis && i.keys_pressed(keybinds.analysis_next) {
+            self.analysis_step(1)
+        }
+        if self.show_analysis && i.keys_pressed(keybinds.analysis_prev) {
+            self.analysis_step(-1)
+        }
+        if !self.is_3d && i.keys_pressed(keybinds.derivative) {
+            self.toggle_companion(self.trace.unwrap_or(0), Companion::Derivative)
+        }
+        if !self.is_3d && i.keys_pressed(keybinds.integral) {
+            self.toggle_companion(self.trace.unwrap_or(0), Companion::Integral)
+        }
+        if !self.is_3d && i.keys_pressed(keybinds.fit) {
+            self.cycle_fit(self.trace.unwrap_or(0))
+        }
+        if !self.fits.is_empty() && i.keys_pressed(keybinds.residuals) {
+            self.show_residuals = !self.show_residuals
+        }
+        if i.keys_pressed(keybinds.export_data) || i.keys_pressed(keybinds.export_visible) {
+            let s = self.export_data(self.export_format, i.keys_pressed(keybinds.export_visible));
+            if self.export_file.is_empty() {
+                self.copy_text(&s);
+            } else if let Err(e) = std::fs::write(&self.export_file, s) {
+                self.error = Some(RuplError::Io(e))
+            }
+        }
+        if !self.fits.is_empty() && i.keys_pressed(keybinds.copy_fit) {
+            self.fit_results();
+            let s = self.fit_list().join("\n");
+            self.copy_text(&s);
+        }
+        if i.keys_pressed(keybinds.trace) {
+            self.trace = if self.trace.is_some() { None } else { Some(0) };
+            self.trace_index = None;
+        }
+        if i.keys_pressed(keybinds.trace_series)
+            && let Some(k) = self.trace.as_mut()
+        {
+            *k = (*k + 1) % self.data.len().max(1);
+            self.trace_index = None;
+        }
+        if self.trace.is_some() {
+            let row = self.trace_row() as isize;
+            for (k, step) in [
+                (keybinds.left, -1),
+                (keybinds.right, 1),
+                (keybinds.up, row),
+                (keybinds.down, -row),
+            ] {
+                if i.keys_pressed(k) {
+                    self.trace_step(step)
+                }
+            }
+            keybinds.left = None;
+            keybinds.right = None;
+            keybinds.up = None;
+            keybinds.down = None;
+        }
+        if i.keys_pressed(keybinds.help) {
+            self.show_help = !self.show_help
+        }
+        if i.keys_pressed(keybinds.rebind) {
+            match self.menu {
+                Menu::Keybinds => {
+                    self.menu = Menu::Normal;
+                    self.text_box = None;
+                    self.select = None;
+                    self.side_drag = None;
+                    self.menu_lines = None;
+                    self.rebind = None;
+                    #[cfg(feature = "serde")]
+                    if !self.keybinds_file.is_empty()
+                        && let Err(e) = self.write_keybinds(&binds)
+                    {
+                        self.error = Some(e.into())
+                    }
+                }
+                _ => {
+                    self.menu = Menu::Keybinds;
+                    self.rebind = None;
+                    self.menu_lines = Some(self.keybind_list(&binds));
+                    self.text_box = Some((0, 0));
+                    self.text_scroll_pos.0 = 0;
+                    self.select = None;
+                    self.side_drag = None;
+                }
+            }
+        }
+        #[cfg(feature = "serde")]
+        if i.keys_pressed(keybinds.load) {
+            match self.menu {
+                Menu::Load => {
+                    self.menu = Menu::Normal;
+                    self.text_box = None;
                     self.select = None;
                     self.side_drag = None;
                 }
                 _ => {
                     self.save();
-                    if !self.file_data.as_ref().unwrap().is_empty() {
+                    if self.file_data.as_ref().is_some_and(|fd| !fd.is_empty()) {
                         self.menu = Menu::Load;
+                        self.load_filter.clear();
+                        self.load_frozen = None;
                         let n = self.save_num.unwrap_or_default();
-                        self.text_box = Some((0, n));
+                        self.text_box = Some((0, n + 1));
                         self.load(n);
                     }
                     self.side_drag = None;
@@ -1786,16 +2638,16 @@ impl Graph {
         }
         #[cfg(feature = "serde")]
         if i.keys_pressed(keybinds.save) {
-            let tiny = self.to_tiny();
-            let seri = bitcode::serialize(&tiny).unwrap();
-            let l = seri.len();
-            let comp = zstd::bulk::compress(&seri, 22).unwrap();
-            let s = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(&comp);
-            let l = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(l.to_string());
-            self.clipboard
-                .as_mut()
-                .unwrap()
-                .set_text(&format!("{l}@{s}"));
+            let s = String::from(&self.to_tiny());
+            self.copy_text(&s);
+        }
+        #[cfg(feature = "serde")]
+        if i.keys_pressed(keybinds.share_data) {
+            let (s, kept, total) = self.to_share(self.share_bits, self.share_limit);
+            self.copy_text(&s);
+            if kept < total && self.error.is_none() {
+                self.error = Some(RuplError::Truncated(kept, total))
+            }
         }
         #[cfg(feature = "serde")]
         if i.keys_pressed(keybinds.full_save) {
@@ -1803,9 +2655,10 @@ impl Graph {
         }
         #[cfg(feature = "serde")]
         if i.keys_pressed(keybinds.paste) {
-            let data = &self.clipboard.as_mut().unwrap().get_text();
-            if let Ok(tiny) = data.try_into() {
-                self.apply_tiny(tiny);
+            let data = self.paste_text();
+            match GraphTiny::decode(&data) {
+                Ok(tiny) => self.apply_tiny(tiny),
+                Err(e) => self.error = Some(e),
             }
         }
         #[cfg(any(feature = "skia", feature = "tiny-skia"))]
@@ -1828,12 +2681,43 @@ impl Graph {
                 let [_, b1, b2, b3] = c.to_be_bytes();
                 [b1, b2, b3, 255]
             }));
-            self.clipboard.as_mut().unwrap().set_image(x, y, &new)
+            if let Some(clipboard) = self.clipboard.as_mut()
+                && let Err(e) = clipboard.set_image(x, y, &new)
+            {
+                self.error = Some(e)
+            }
         }
         if !self.mouse_held && ret {
-            self.keybinds = Some(keybinds);
+            self.keybinds = Some(binds);
             return;
         }
+        if i.pointer == Some(true)
+            && i.modifiers.shift
+            && i.multi.is_none()
+            && matches!(self.menu, Menu::Side | Menu::Normal)
+            && let Some(pos) = self.mouse_position
+            && pos.x > 0.0
+        {
+            self.zoom_box = Some(pos)
+        }
+        if i.pointer == Some(true)
+            && !i.modifiers.shift
+            && matches!(self.menu, Menu::Side | Menu::Normal)
+            && let Some(pos) = self.mouse_position
+            && pos.x > 0.0
+            && let Some((_, k, n, _, _)) = self.point_index.nearest(pos.to_pos())
+            && let Some(f) = self.point_click.as_mut()
+        {
+            f(k, n)
+        }
+        if let Some(start) = self.zoom_box
+            && i.pointer.is_none()
+        {
+            self.zoom_box = None;
+            if let Some(end) = self.mouse_position {
+                self.box_zoom(start, end)
+            }
+        }
         match &i.multi {
             Some(multi) => {
                 self.last_multi = true;
@@ -1896,6 +2780,7 @@ impl Graph {
             _ if i.pointer.is_some() => {
                 if !i.pointer.unwrap_or(false)
                     && !self.last_multi
+                    && self.zoom_box.is_none()
                     && let (Some(interact), Some(last)) = (i.pointer_pos, self.last_interact)
                 {
                     let delta = interact - last;
@@ -1925,7 +2810,7 @@ impl Graph {
         }
         self.last_interact = i.pointer_pos;
         if ret {
-            self.keybinds = Some(keybinds);
+            self.keybinds = Some(binds);
             return;
         }
         let (ax, ay, b, c) = (
@@ -1964,7 +2849,7 @@ impl Graph {
             if self.is_3d {
                 self.angle.y = ((self.angle.y / b - 1.0).round() * b).rem_euclid(TAU);
             } else {
-                if self.graph_mode == GraphMode::DomainColoring {
+                if self.is_image() {
                     self.recalculate(None);
                 }
                 self.offset.y += ay;
@@ -1974,7 +2859,7 @@ impl Graph {
             if self.is_3d {
                 self.angle.y = ((self.angle.y / b + 1.0).round() * b).rem_euclid(TAU);
             } else {
-                if self.graph_mode == GraphMode::DomainColoring {
+                if self.is_image() {
                     self.recalculate(None);
                 }
                 self.offset.y -= ay;
@@ -2241,6 +3126,31 @@ impl Graph {
             self.cache = None;
             self.log_scale = !self.log_scale
         }
+        if self.graph_mode == GraphMode::DomainColoring && i.keys_pressed(keybinds.contour) {
+            self.contours = None;
+            self.show_contour = !self.show_contour
+        }
+        if self.graph_mode == GraphMode::Conformal && i.keys_pressed(keybinds.conformal_polar) {
+            self.conformal_polar = !self.conformal_polar
+        }
+        if self.graph_mode == GraphMode::DomainColoring && i.keys_pressed(keybinds.zeros) {
+            self.zeros = None;
+            self.show_zeros = !self.show_zeros
+        }
+        if (self.graph_mode == GraphMode::DomainColoring || self.is_3d)
+            && i.keys_pressed(keybinds.branch_cut)
+        {
+            self.branch_cuts = None;
+            self.seams = None;
+            self.show_branch = !self.show_branch
+        }
+        if self.graph_mode == GraphMode::DomainColoring
+            && self.show_zeros
+            && i.keys_pressed(keybinds.copy_zeros)
+        {
+            let s = self.zeros_list().join("\n");
+            self.copy_text(&s);
+        }
         if i.keys_pressed(keybinds.line_style) {
             self.lines = match self.lines {
                 Lines::Lines => Lines::Points,
@@ -2310,6 +3220,8 @@ impl Graph {
                 GraphMode::Flatten,
                 GraphMode::Depth,
                 GraphMode::DomainColoring,
+                GraphMode::RiemannSphere,
+                GraphMode::Conformal,
             ],
             (true, false) => vec![
                 GraphMode::Normal,
@@ -2322,6 +3234,7 @@ impl Graph {
                 GraphMode::Polar,
                 GraphMode::Slice,
                 GraphMode::SlicePolar,
+                GraphMode::Heatmap,
             ],
             (false, false) => vec![GraphMode::Normal, GraphMode::Polar],
         };
@@ -2340,6 +3253,21 @@ impl Graph {
             self.reduced_move = !self.reduced_move;
             self.recalculate(None);
         }
+        if i.keys_pressed(keybinds.zoom_previous)
+            && let Some(view) = self.zoom_history.pop()
+        {
+            self.set_view(view)
+        }
+        if i.keys_pressed(keybinds.view_back) && self.view_pos + 1 < self.view_history.len() {
+            self.view_pos += 1;
+            self.view_merge = None;
+            self.set_view(self.view_history[self.view_history.len() - 1 - self.view_pos])
+        }
+        if i.keys_pressed(keybinds.view_forward) && self.view_pos != 0 {
+            self.view_pos -= 1;
+            self.view_merge = None;
+            self.set_view(self.view_history[self.view_history.len() - 1 - self.view_pos])
+        }
         if i.keys_pressed(keybinds.reset) {
             self.offset3d = Vec3::splat(0.0);
             self.offset = Vec2::splat(0.0);
@@ -2354,111 +3282,240 @@ impl Graph {
             self.mouse_moved = false;
             self.recalculate(None);
         }
-        self.keybinds = Some(keybinds)
+        self.keybinds = Some(binds)
     }
     #[cfg(feature = "serde")]
+    ///saves into Graph.save_file, showing any error
     pub(crate) fn save(&mut self) {
-        if self.file_data_raw.is_some() {
-            let fd = self.file_data_raw.as_mut().unwrap();
-            let n = self.file_data.as_ref().unwrap();
-            update_saves(fd, n);
+        if let Err(e) = self.try_save() {
+            self.error = Some(e)
         }
+    }
+    #[cfg(feature = "serde")]
+    ///saves the graph into Graph.save_file, replacing its previous save if it was loaded from there
+    pub fn try_save(&mut self) -> Result<(), RuplError> {
+        let text = is_text_save(&self.save_file);
         let offset = self.to_coord((self.screen / 2.0).to_pos()).into();
         let offset = std::mem::replace(&mut self.offset, offset);
-        let seri = bitcode::serialize(&self).unwrap();
+        let encoded = encode_save(self, text);
         self.offset = offset;
-        let l = seri.len();
-        let comp = zstd::bulk::compress(&seri, 22).unwrap();
-        let s = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(&comp);
-        let l = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(l.to_string());
-        let n = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(
-            self.names
-                .iter()
-                .filter_map(|n| {
-                    if n.name.is_empty() {
-                        None
-                    } else {
-                        Some(n.name.as_str())
-                    }
-                })
-                .next()
-                .unwrap_or(""),
-        );
-        if !std::fs::exists(&self.save_file).unwrap() {
-            std::fs::File::create(&self.save_file).unwrap();
-        }
-        if self.file_data_raw.is_none() {
-            let file = std::fs::File::open(&self.save_file).unwrap();
-            self.file_data_raw = Some(
-                std::io::BufReader::new(file)
-                    .lines()
-                    .map(Result::unwrap)
-                    .collect::<Vec<String>>(),
-            );
+        let (len, data) = encoded?;
+        let name = self
+            .names
+            .iter()
+            .filter_map(|n| {
+                if n.name.is_empty() {
+                    None
+                } else {
+                    Some(n.name.as_str())
+                }
+            })
+            .next()
+            .unwrap_or("")
+            .to_string();
+        let mut entry = SaveEntry {
+            name,
+            len,
+            data,
+            version: SAVE_VERSION,
+            meta: self.save_meta(),
+            raw: None,
+        };
+        if !std::fs::exists(&self.save_file)? {
+            std::fs::File::create(&self.save_file)?;
         }
-        let Some(file_data) = self.file_data_raw.as_mut() else {
-            unreachable!()
+        let mut file_data = match (&self.file_data_raw, &self.file_data) {
+            (Some(raw), Some(n)) => {
+                let mut raw = raw.clone();
+                update_saves(&mut raw, n, text);
+                raw
+            }
+            (Some(raw), None) => raw.clone(),
+            (None, _) => read_saves(&self.save_file, text)?,
         };
+        let mut save_num = self.save_num;
         let do_save = self.names.iter().any(|n| !n.name.is_empty()) && !self.data.is_empty();
-        if do_save || self.save_num.is_some() {
-            if let Some(i) = self.save_num {
-                if do_save {
-                    let s = format!("{n}@{l}@{s}");
-                    file_data[i] = s
-                } else {
-                    let Some(fd) = &mut self.file_data else {
-                        unreachable!()
-                    };
-                    fd.remove(i);
-                    self.save_num = None;
-                    update_saves(file_data, fd);
+        if do_save || save_num.is_some() {
+            match save_num {
+                Some(i) if i < file_data.len() => {
+                    if do_save {
+                        if let Some(e) = self.file_data.as_ref().and_then(|fd| fd.get(i)) {
+                            if e.version >= 2 && e.meta.names.first() != Some(&e.name) {
+                                entry.name = e.name.clone()
+                            }
+                            if entry.meta.thumbnail.is_empty() {
+                                entry.meta.thumbnail = e.meta.thumbnail.clone()
+                            }
+                        }
+                        file_data[i] = format_save(&entry, text)
+                    } else {
+                        file_data.remove(i);
+                        save_num = None;
+                    }
                 }
-            } else {
-                let s = format!("{n}@{l}@{s}");
-                self.save_num = Some(file_data.len());
-                file_data.push(s);
+                _ if do_save => {
+                    save_num = Some(file_data.len());
+                    file_data.push(format_save(&entry, text));
+                }
+                _ => save_num = None,
             }
-            let parent = std::path::Path::new(&self.save_file).parent().unwrap();
-            if !std::fs::exists(parent).unwrap() {
-                std::fs::create_dir_all(parent).unwrap()
+            if let Some(parent) = std::path::Path::new(&self.save_file).parent()
+                && !std::fs::exists(parent)?
+            {
+                std::fs::create_dir_all(parent)?
             }
-            std::fs::write(&self.save_file, file_data.join("\n")).unwrap();
+            write_saves(&self.save_file, &file_data, text)?;
         }
-        self.file_data = Some(
-            file_data
-                .iter()
-                .map(|s| {
-                    let r = s.rsplitn(3, '@').collect::<Vec<&str>>();
-                    let s = |s: &str| {
-                        String::from_utf8(
-                            base64::prelude::BASE64_URL_SAFE_NO_PAD.decode(s).unwrap(),
-                        )
-                        .unwrap()
-                    };
-                    let a = s(r[2]);
-                    let c = s(r[1]).parse::<usize>().unwrap();
-                    (a, c, r[0].to_string())
+        self.save_num = save_num;
+        let mut error = None;
+        let entries = file_data
+            .iter()
+            .map(|s| {
+                parse_save(s, text).unwrap_or_else(|e| {
+                    error.get_or_insert(e);
+                    SaveEntry {
+                        raw: Some(s.clone()),
+                        ..SaveEntry::default()
+                    }
                 })
+            })
+            .collect();
+        self.file_data = Some(entries);
+        self.file_data_raw = Some(file_data);
+        if let Some(e) = error {
+            self.error = Some(e)
+        }
+        Ok(())
+    }
+    #[cfg(feature = "serde")]
+    ///details of the current graph stored alongside its save
+    fn save_meta(&mut self) -> SaveMeta {
+        #[cfg(not(target_arch = "wasm32"))]
+        let time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        #[cfg(target_arch = "wasm32")]
+        let time = 0;
+        SaveMeta {
+            time,
+            graph_mode: self.graph_mode,
+            names: self
+                .names
+                .iter()
+                .filter(|n| !n.name.is_empty())
+                .map(|n| n.name.clone())
                 .collect(),
-        );
+            thumbnail: self.thumbnail(),
+        }
+    }
+    #[cfg(feature = "serde")]
+    ///takes the thumbnail stored with the next save from a frame of rgba pixels of the given
+    ///width and height, for backends that can not read their frame back like skia-vulkan,
+    ///egui and wasm, the frame should be the one last drawn by update
+    pub fn set_thumbnail(&mut self, rgba: &[u8], width: usize, height: usize) {
+        if rgba.len() < 4 * width * height {
+            return;
+        }
+        let pixel = |x: usize, y: usize| {
+            let k = 4 * (y * width + x);
+            [rgba[k], rgba[k + 1], rgba[k + 2]]
+        };
+        self.frame_thumbnail = thumbnail(pixel, self.draw_offset, self.screen, (width, height));
+    }
+    #[cfg(feature = "serde")]
+    ///thumbnail of the plot in the last frame, or the one passed to set_thumbnail,
+    ///empty if the frame can not be read back
+    fn thumbnail(&mut self) -> Vec<u8> {
+        let host = std::mem::take(&mut self.frame_thumbnail);
+        #[cfg(feature = "tiny-skia")]
+        if let Some(canvas) = &self.canvas {
+            let (width, data) = (canvas.width() as usize, canvas.data());
+            let pixel = |x: usize, y: usize| {
+                let k = 4 * (y * width + x);
+                [data[k], data[k + 1], data[k + 2]]
+            };
+            let frame = (width, canvas.height() as usize);
+            return thumbnail(pixel, self.draw_offset, self.screen, frame);
+        }
+        #[cfg(all(feature = "skia", not(feature = "skia-vulkan")))]
+        if let Some(pixmap) = self.canvas.as_mut().and_then(|c| c.peek_pixels())
+            && let Some(data) = pixmap.bytes()
+        {
+            let row = pixmap.row_bytes();
+            let pixel = |x: usize, y: usize| {
+                let k = y * row + 4 * x;
+                [data[k + 2], data[k + 1], data[k]]
+            };
+            let frame = (pixmap.width() as usize, pixmap.height() as usize);
+            return thumbnail(pixel, self.draw_offset, self.screen, frame);
+        }
+        host
+    }
+    #[cfg(feature = "serde")]
+    ///rewrites every save in the save file at from into the save file at to,
+    ///converting between the json and compressed formats as chosen by their extensions,
+    ///and migrating older versions to the current one
+    pub fn convert_save_file(from: &str, to: &str) -> Result<(), RuplError> {
+        let (a, b) = (is_text_save(from), is_text_save(to));
+        let saves = read_saves(from, a)?
+            .iter()
+            .map(|s| {
+                let e = parse_save(s, a)?;
+                let mut graph = decode_save(e.len, &e.data, e.version, a)?;
+                graph.save_num = None;
+                let (len, data) = encode_save(&graph, b)?;
+                let meta = if e.version >= 2 {
+                    e.meta
+                } else {
+                    SaveMeta {
+                        time: 0,
+                        ..graph.save_meta()
+                    }
+                };
+                Ok(format_save(
+                    &SaveEntry {
+                        name: e.name,
+                        len,
+                        data,
+                        version: SAVE_VERSION,
+                        meta,
+                        raw: None,
+                    },
+                    b,
+                ))
+            })
+            .collect::<Result<Vec<String>, RuplError>>()?;
+        write_saves(to, &saves, b)
     }
     #[cfg(feature = "serde")]
+    ///loads the jth save, showing any error
     pub(crate) fn load(&mut self, j: usize) {
+        if let Err(e) = self.try_load(j) {
+            self.error = Some(e)
+        }
+    }
+    #[cfg(feature = "serde")]
+    ///saves the current graph then loads the jth save of Graph.save_file,
+    ///keeping the current graph if it can not be loaded
+    pub fn try_load(&mut self, j: usize) -> Result<(), RuplError> {
         if Some(j) == self.save_num {
-            return;
+            return Ok(());
         }
-        self.save();
-        let fd = self.file_data.as_ref().unwrap();
-        if fd.is_empty() {
-            return;
+        self.try_save()?;
+        let Some(e) = self.file_data.as_ref().and_then(|fd| fd.get(j)) else {
+            return Ok(());
+        };
+        if e.raw.is_some() {
+            return Err(RuplError::Decode(format!("save {j} could not be read")));
         }
-        let (_, n, s) = &fd[j];
-        let s = base64::prelude::BASE64_URL_SAFE_NO_PAD.decode(s).unwrap();
-        let data = zstd::bulk::decompress(&s, *n).unwrap();
-        let mut graph: Graph = bitcode::deserialize(&data).unwrap();
+        let mut graph = decode_save(e.len, &e.data, e.version, is_text_save(&self.save_file))?;
         graph.save_num = Some(j);
         graph.file_data = std::mem::take(&mut self.file_data);
         graph.file_data_raw = std::mem::take(&mut self.file_data_raw);
+        graph.load_filter = std::mem::take(&mut self.load_filter);
+        graph.load_frozen = std::mem::take(&mut self.load_frozen);
+        graph.share_limit = self.share_limit;
+        graph.share_bits = self.share_bits;
         graph.clipboard = std::mem::take(&mut self.clipboard);
         graph.menu = self.menu;
         #[cfg(any(feature = "skia", feature = "tiny-skia-text"))]
@@ -2485,6 +3542,7 @@ impl Graph {
         }
         self.save_num = None;
         *self = graph;
+        Ok(())
     }
     #[cfg(feature = "egui")]
     fn plot(&mut self, painter: &mut Painter, ui: &egui::Ui) -> Option<Vec<(f32, Draw, Color)>> {
@@ -2562,6 +3620,7 @@ impl Graph {
                     GraphType::Width3D(d, _, _, _, _) => d.len(),
                     GraphType::Constant(_, _) => 0,
                     GraphType::Point(_) => 0,
+                    GraphType::Surface(d, _, _, _, _, _) => d.iter().map(|d| d.len()).sum(),
                     GraphType::List(a) => a.iter().map(su).sum(),
                     GraphType::None => 0,
                 }
@@ -2580,6 +3639,78 @@ impl Graph {
             Vec::with_capacity(n + 12)
         });
         let mut cache = std::mem::take(&mut self.cache);
+        if self.graph_mode == GraphMode::Heatmap && cache.is_none() {
+            self.heatmap_bound = self
+                .heatmap_range
+                .unwrap_or_else(|| heatmap_bound(&self.data));
+        }
+        if self.graph_mode == GraphMode::DomainColoring
+            && self.show_contour
+            && (cache.is_none() || self.contours.is_none())
+        {
+            let lenx = (self.screen.x * self.prec() * self.mult) as usize;
+            let leny = (self.screen.y * self.prec() * self.mult) as usize;
+            let mut contours = Vec::new();
+            for data in &self.data {
+                self.get_contours(data, lenx, leny, &mut contours);
+            }
+            self.contours = Some(contours);
+        }
+        if self.graph_mode == GraphMode::DomainColoring
+            && self.show_zeros
+            && (cache.is_none() || self.zeros.is_none())
+        {
+            let lenx = (self.screen.x * self.prec() * self.mult) as usize;
+            let leny = (self.screen.y * self.prec() * self.mult) as usize;
+            let mut zeros = Vec::new();
+            for data in &self.data {
+                get_zeros(data, lenx, leny, &mut zeros);
+            }
+            self.zeros = Some(zeros);
+        }
+        if self.graph_mode == GraphMode::DomainColoring
+            && self.show_branch
+            && (cache.is_none() || self.branch_cuts.is_none())
+        {
+            let lenx = (self.screen.x * self.prec() * self.mult) as usize;
+            let leny = (self.screen.y * self.prec() * self.mult) as usize;
+            let mut cuts = Vec::new();
+            for data in &self.data {
+                get_branch_cuts(data, lenx, leny, &mut cuts);
+            }
+            self.branch_cuts = Some(cuts);
+        }
+        if self.is_3d
+            && self.graph_mode == GraphMode::Normal
+            && self.show_branch
+            && self.seams.is_none()
+        {
+            self.seams = Some(
+                self.data
+                    .iter()
+                    .map(|data| {
+                        let mut cuts = Vec::new();
+                        get_seams(data, &mut cuts);
+                        cuts
+                    })
+                    .collect(),
+            );
+        }
+        if !self.is_3d && (self.show_analysis || !self.fits.is_empty()) {
+            let hash = data_hash(&self.data);
+            if hash != self.data_hash {
+                self.data_hash = hash;
+                self.analysis = None;
+                self.fit_results = None;
+            }
+        }
+        if self.show_analysis
+            && !self.is_3d
+            && self.graph_mode == GraphMode::Normal
+            && self.analysis.is_none()
+        {
+            self.analysis = Some(self.get_analysis());
+        }
         let mut image_buffer = std::mem::take(&mut self.image_buffer);
         for (k, data) in self.data.iter().enumerate() {
             self.plot_type(
@@ -2592,10 +3723,297 @@ impl Graph {
                 &mut image_buffer,
             );
         }
+        for (j, (k, c)) in self.companions.iter().enumerate() {
+            if let Some(data) = self.companion_data(*k, *c) {
+                self.plot_type(
+                    painter,
+                    &tex,
+                    &mut buffer,
+                    self.data.len() + j,
+                    &data,
+                    &mut cache,
+                    &mut image_buffer,
+                );
+            }
+        }
+        if !self.is_3d && !self.fits.is_empty() {
+            self.fit_results();
+            let n = self.data.len() + self.companions.len();
+            for (j, (curve, residuals)) in self.fit_data().into_iter().enumerate() {
+                self.plot_type(
+                    painter,
+                    &tex,
+                    &mut buffer,
+                    n + j,
+                    &curve,
+                    &mut cache,
+                    &mut image_buffer,
+                );
+                if let Some(residuals) = residuals {
+                    self.plot_type(
+                        painter,
+                        &tex,
+                        &mut buffer,
+                        n + j,
+                        &residuals,
+                        &mut cache,
+                        &mut image_buffer,
+                    );
+                }
+            }
+        }
         self.cache = cache;
         self.image_buffer = image_buffer;
+        self.point_index = self.get_point_index();
         buffer
     }
+    ///the branch cuts of a Width3D grid of the kth series, found by its position in the series
+    fn cached_seams(&self, k: usize, data: &Vec<Complex>) -> Option<&(Vec<bool>, Vec<bool>)> {
+        fn find(series: &GraphType, data: &Vec<Complex>, n: &mut usize) -> bool {
+            match series {
+                GraphType::List(a) => a.iter().any(|series| find(series, data, n)),
+                GraphType::Width3D(d, _, _, _, _) if std::ptr::eq(d, data) => true,
+                GraphType::Width3D(..) => {
+                    *n += 1;
+                    false
+                }
+                _ => false,
+            }
+        }
+        if !self.show_branch {
+            return None;
+        }
+        let mut n = 0;
+        find(self.data.get(k)?, data, &mut n)
+            .then(|| self.seams.as_ref()?.get(k)?.get(n))
+            .flatten()
+    }
+    ///indexes the screen positions of Coord and Point data for hovering and clicking
+    fn get_point_index(&self) -> PointIndex {
+        fn push(g: &Graph, data: &GraphType, k: usize, n: &mut usize, index: &mut PointIndex) {
+            match data {
+                GraphType::Coord(data) => {
+                    for (x, z) in data {
+                        let (re, im) = z.to_options();
+                        let v = [re.filter(|_| g.show.real()), im.filter(|_| g.show.imag())];
+                        let pts = match g.graph_mode {
+                            GraphMode::Normal if !g.is_3d => v.map(|v| Some(g.to_screen(*x, v?))),
+                            GraphMode::Polar if !g.is_3d => {
+                                let (s, c) = x.sin_cos();
+                                v.map(|v| Some(g.to_screen(c * v?, s * v?)))
+                            }
+                            GraphMode::Flatten | GraphMode::Depth => {
+                                [g.trace_pos(*x, None, *z), None]
+                            }
+                            _ => [None, None],
+                        };
+                        for p in pts.into_iter().flatten() {
+                            index.insert(p, k, *n, *x, *z)
+                        }
+                        *n += 1;
+                    }
+                }
+                GraphType::Point(p) => {
+                    if !g.is_3d {
+                        let pos = match g.graph_mode {
+                            GraphMode::Polar | GraphMode::SlicePolar => {
+                                let (s, c) = p.x.sin_cos();
+                                Some(g.to_screen(c * p.y, s * p.y))
+                            }
+                            GraphMode::Depth | GraphMode::RiemannSphere | GraphMode::Conformal => {
+                                None
+                            }
+                            _ => Some(g.to_screen(p.x, p.y)),
+                        };
+                        if let Some(pos) = pos {
+                            index.insert(pos, k, *n, p.x, Complex::Real(p.y))
+                        }
+                    }
+                    *n += 1;
+                }
+                GraphType::List(a) => a.iter().for_each(|data| push(g, data, k, n, index)),
+                _ => {}
+            }
+        }
+        let mut index = PointIndex::new(16.0);
+        for (k, data) in self.data.iter().enumerate() {
+            push(self, data, k, &mut 0, &mut index)
+        }
+        index
+    }
+    ///names of the series in the order of Graph.data, names which are empty or hidden have no data
+    pub(crate) fn series_list(&self) -> Vec<&Name> {
+        let blacklist = self
+            .blacklist_graphs
+            .iter()
+            .filter_map(|i| self.index_to_name(*i, true).0)
+            .collect::<Vec<usize>>();
+        self.names
+            .iter()
+            .enumerate()
+            .filter(|(i, n)| !n.name.is_empty() && !blacklist.contains(i))
+            .map(|(_, n)| n)
+            .collect()
+    }
+    ///name of the kth series
+    pub(crate) fn series(&self, k: usize) -> Option<&Name> {
+        self.series_list().get(k).copied()
+    }
+    pub(crate) fn series_name(&self, k: usize) -> &str {
+        self.series(k).map(|n| n.name.as_str()).unwrap_or_default()
+    }
+    fn companion_name(&self, k: usize, c: Companion) -> String {
+        let name = self.series_name(k);
+        match c {
+            Companion::Derivative => format!("d/dx {name}"),
+            Companion::Integral => format!("∫{name} dx"),
+        }
+    }
+    ///shows or hides a derivative or integral curve of the kth series
+    pub fn toggle_companion(&mut self, k: usize, c: Companion) {
+        if let Some(i) = self.companions.iter().position(|a| *a == (k, c)) {
+            self.companions.remove(i);
+        } else {
+            self.companions.push((k, c))
+        }
+    }
+    ///derivative or integral of the kth series if it is Width or Coord data,
+    ///the real and imaginary parts are treated separately
+    fn companion_data(&self, k: usize, c: Companion) -> Option<GraphType> {
+        let (xs, zs): (Vec<f64>, Vec<Complex>) = match self.data.get(k)? {
+            GraphType::Width(data, s, e) => (
+                (0..data.len())
+                    .map(|i| s + i as f64 / (data.len().max(2) - 1) as f64 * (e - s))
+                    .collect(),
+                data.clone(),
+            ),
+            GraphType::Coord(data) => {
+                let mut data = data.clone();
+                data.sort_by(|a, b| a.0.total_cmp(&b.0));
+                //samples sharing an x are averaged so no difference has a zero width
+                data.chunk_by(|a, b| a.0 == b.0)
+                    .map(|c| match c {
+                        [a] => *a,
+                        _ => {
+                            let n = c.len() as f64;
+                            let add = |s: Option<f64>, v: Option<f64>| {
+                                v.map(|v| s.unwrap_or(0.0) + v).or(s)
+                            };
+                            let (re, im) = c.iter().fold((None, None), |(re, im), (_, z)| {
+                                let (a, b) = z.to_options();
+                                (add(re, a), add(im, b))
+                            });
+                            let z = match (re, im) {
+                                (Some(re), Some(im)) => Complex::Complex(re / n, im / n),
+                                (None, Some(im)) => Complex::Imag(im / n),
+                                (re, None) => Complex::Real(re.unwrap_or(f64::NAN) / n),
+                            };
+                            (c[0].0, z)
+                        }
+                    })
+                    .unzip()
+            }
+            _ => return None,
+        };
+        let n = xs.len();
+        if n < 2 {
+            return None;
+        }
+        let (has_re, has_im) = zs.iter().fold((false, false), |(r, i), z| {
+            let (a, b) = z.to_options();
+            (r || a.is_some(), i || b.is_some())
+        });
+        let parts = zs
+            .iter()
+            .map(|z| {
+                let (a, b) = z.to_options();
+                (a.unwrap_or(0.0), b.unwrap_or(0.0))
+            })
+            .collect::<Vec<(f64, f64)>>();
+        let out = match c {
+            Companion::Derivative => (0..n)
+                .map(|i| {
+                    let (a, b) = (i.saturating_sub(1), (i + 1).min(n - 1));
+                    let dx = xs[b] - xs[a];
+                    (
+                        (parts[b].0 - parts[a].0) / dx,
+                        (parts[b].1 - parts[a].1) / dx,
+                    )
+                })
+                .collect::<Vec<(f64, f64)>>(),
+            Companion::Integral => {
+                let mut sum = (0.0, 0.0);
+                let mut out = vec![sum];
+                for i in 1..n {
+                    let dx = (xs[i] - xs[i - 1]) * 0.5;
+                    let (re, im) = (
+                        (parts[i].0 + parts[i - 1].0) * dx,
+                        (parts[i].1 + parts[i - 1].1) * dx,
+                    );
+                    if re.is_finite() {
+                        sum.0 += re
+                    }
+                    if im.is_finite() {
+                        sum.1 += im
+                    }
+                    out.push(sum)
+                }
+                out
+            }
+        };
+        let zs = out
+            .into_iter()
+            .map(|(re, im)| match (has_re, has_im) {
+                (true, true) => Complex::Complex(re, im),
+                (false, true) => Complex::Imag(im),
+                _ => Complex::Real(re),
+            })
+            .collect::<Vec<Complex>>();
+        Some(match self.data.get(k)? {
+            GraphType::Width(_, s, e) => GraphType::Width(zs, *s, *e),
+            _ => GraphType::Coord(xs.into_iter().zip(zs).collect()),
+        })
+    }
+    fn write_hover(&self, painter: &mut Painter) {
+        let Some(pos) = self.mouse_position.filter(|_| self.mouse_moved) else {
+            return;
+        };
+        let Some((p, k, n, x, z)) = self.point_index.nearest(pos.to_pos()) else {
+            return;
+        };
+        painter.circle(
+            p,
+            2.0 * self.point_size,
+            &self.select_color,
+            self.line_width,
+        );
+        let name = self.series_name(k);
+        let (re, im) = z.to_options();
+        let mut lines = vec![format!("{name} {n}"), format!("{x:E}")];
+        lines.extend(re.map(|re| format!("{re:E}")));
+        lines.extend(im.map(|im| format!("{im:E}")));
+        let h = self.font_size;
+        let width = lines
+            .iter()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or_default() as f32
+            * self.font_width
+            + h;
+        let height = h * lines.len() as f32;
+        let x = (p.x + h).min(self.screen.x as f32 - width).max(0.0);
+        let y = (p.y + h).min(self.screen.y as f32 - height).max(0.0);
+        painter.highlight(x, y, x + width, y + height, &self.background_color);
+        for (i, l) in lines.iter().enumerate() {
+            self.text(
+                Pos::new(x + h * 0.5, y + h * (i as f32 + 0.5)),
+                Align::LeftCenter,
+                l,
+                &self.text_color,
+                painter,
+            );
+        }
+    }
     #[allow(clippy::too_many_arguments)]
     fn plot_type<G>(
         &self,
@@ -2612,11 +4030,39 @@ impl Graph {
         let (mut a, mut b, mut c) = (None, None, None);
         match data {
             GraphType::None => {}
+            GraphType::Surface(data, start_x, start_y, end_x, end_y, connect) => {
+                if self.graph_mode == GraphMode::Normal && self.is_3d {
+                    self.plot_surface(
+                        data, *start_x, *start_y, *end_x, *end_y, connect, buffer, painter,
+                    )
+                }
+            }
+            GraphType::List(a) if self.graph_mode == GraphMode::RiemannSphere => {
+                for (n, data) in a.iter().enumerate() {
+                    if let GraphType::Width3D(data, start_x, start_y, end_x, end_y) = data {
+                        self.plot_sphere(
+                            data,
+                            *start_x,
+                            *start_y,
+                            *end_x,
+                            *end_y,
+                            n % 2 == 1,
+                            buffer,
+                            painter,
+                        )
+                    }
+                }
+            }
             GraphType::List(a) => a.iter().for_each(|data| {
                 self.plot_type(painter, tex, buffer, k, data, cache, image_buffer)
             }),
             GraphType::Width(data, start, end) => match self.graph_mode {
-                GraphMode::DomainColoring | GraphMode::Slice | GraphMode::SlicePolar => {}
+                GraphMode::DomainColoring
+                | GraphMode::Heatmap
+                | GraphMode::RiemannSphere
+                | GraphMode::Conformal
+                | GraphMode::Slice
+                | GraphMode::SlicePolar => {}
                 GraphMode::Normal => {
                     for (i, y) in data.iter().enumerate() {
                         let x = (i as f64 / (data.len() - 1) as f64 - 0.5) * (end - start)
@@ -2739,7 +4185,12 @@ impl Graph {
                 }
             },
             GraphType::Coord(data) => match self.graph_mode {
-                GraphMode::DomainColoring | GraphMode::Slice | GraphMode::SlicePolar => {}
+                GraphMode::DomainColoring
+                | GraphMode::Heatmap
+                | GraphMode::RiemannSphere
+                | GraphMode::Conformal
+                | GraphMode::Slice
+                | GraphMode::SlicePolar => {}
                 GraphMode::Normal => {
                     for (x, y) in data {
                         let (y, z) = y.to_options();
@@ -2862,8 +4313,10 @@ impl Graph {
                     let mut cur = Vec::with_capacity(len);
                     let mut lasti = Vec::with_capacity(len);
                     let mut curi = Vec::with_capacity(len);
+                    let cuts = self.cached_seams(k, data);
                     for (i, z) in data.iter().enumerate() {
                         let (i, j) = (i % len, i / len);
+                        let (cut_a, cut_b, seam_a, seam_b) = seams(cuts, len, i, j);
                         let x = (i as f64 / (len - 1) as f64 - 0.5) * (end_x - start_x)
                             + (start_x + end_x) * 0.5;
                         let y = (j as f64 / (len - 1) as f64 - 0.5) * (end_y - start_y)
@@ -2885,16 +4338,31 @@ impl Graph {
                                 }
                                 None
                             } else {
-                                self.draw_point_3d(
+                                let p = self.draw_point_3d(
                                     x,
                                     y,
                                     w,
                                     &self.alt_colors[k % self.alt_colors.len()],
-                                    if i == 0 { None } else { curi[i - 1] },
-                                    if j == 0 { None } else { lasti[i] },
+                                    if i == 0 || cut_a || seam_a {
+                                        None
+                                    } else {
+                                        curi[i - 1]
+                                    },
+                                    if j == 0 || cut_b || seam_b {
+                                        None
+                                    } else {
+                                        lasti[i]
+                                    },
                                     buffer,
                                     painter,
-                                )
+                                );
+                                if seam_a {
+                                    self.draw_seam(p, curi[i - 1], buffer, painter)
+                                }
+                                if seam_b {
+                                    self.draw_seam(p, lasti[i], buffer, painter)
+                                }
+                                p
                             }
                         } else {
                             None
@@ -2906,16 +4374,31 @@ impl Graph {
                         let p = if !self.show.real() {
                             None
                         } else if let Some(z) = z {
-                            self.draw_point_3d(
+                            let p = self.draw_point_3d(
                                 x,
                                 y,
                                 z,
                                 &self.main_colors[k % self.main_colors.len()],
-                                if i == 0 { None } else { cur[i - 1] },
-                                if j == 0 { None } else { last[i] },
-                                buffer,
-                                painter,
-                            )
+                                if i == 0 || cut_a || seam_a {
+                                    None
+                                } else {
+                                    cur[i - 1]
+                                },
+                                if j == 0 || cut_b || seam_b {
+                                    None
+                                } else {
+                                    last[i]
+                                },
+                                buffer,
+                                painter,
+                            );
+                            if seam_a {
+                                self.draw_seam(p, cur[i - 1], buffer, painter)
+                            }
+                            if seam_b {
+                                self.draw_seam(p, last[i], buffer, painter)
+                            }
+                            p
                         } else {
                             None
                         };
@@ -3181,10 +4664,66 @@ impl Graph {
                         painter.image(texture, self.screen);
                     }
                 }
+                GraphMode::Conformal => {
+                    self.plot_conformal(painter, k, data, *start_x, *start_y, *end_x, *end_y)
+                }
+                GraphMode::RiemannSphere => self.plot_sphere(
+                    data, *start_x, *start_y, *end_x, *end_y, false, buffer, painter,
+                ),
+                GraphMode::Heatmap => {
+                    let lenx = (self.screen.x * self.prec() * self.mult) as usize;
+                    let leny = (self.screen.y * self.prec() * self.mult) as usize;
+                    if cache.is_none() {
+                        #[cfg(feature = "egui")]
+                        let m = 3;
+                        #[cfg(any(feature = "skia", feature = "tiny-skia", feature = "wasm-draw"))]
+                        let m = 4;
+                        let n = lenx * leny * m;
+                        let c = image_buffer.len();
+                        if c < n {
+                            image_buffer.resize(n, 0);
+                        }
+                        let (min, max) = (self.heatmap_bound.x, self.heatmap_bound.y);
+                        for (i, z) in data.iter().enumerate() {
+                            let [r, g, b] = match z.to_options().0 {
+                                Some(z) if z.is_finite() => {
+                                    let (r, g, b) = self.colormap.to_rgb((z - min) / (max - min));
+                                    rgb2val(r, g, b)
+                                }
+                                _ => {
+                                    let c = self.background_color;
+                                    rgb2val(
+                                        c.r as f64 / 255.0,
+                                        c.g as f64 / 255.0,
+                                        c.b as f64 / 255.0,
+                                    )
+                                }
+                            };
+                            image_buffer[m * i] = r;
+                            image_buffer[m * i + 1] = g;
+                            image_buffer[m * i + 2] = b;
+                            #[cfg(any(
+                                feature = "skia",
+                                feature = "tiny-skia",
+                                feature = "wasm-draw"
+                            ))]
+                            {
+                                image_buffer[m * i + 3] = 255;
+                            }
+                        }
+                        tex(cache, lenx, leny, image_buffer);
+                    }
+                    if let Some(texture) = cache {
+                        painter.image(texture, self.screen);
+                    }
+                }
             },
             GraphType::Coord3D(data) => match self.graph_mode {
                 GraphMode::Slice
                 | GraphMode::DomainColoring
+                | GraphMode::Heatmap
+                | GraphMode::RiemannSphere
+                | GraphMode::Conformal
                 | GraphMode::Flatten
                 | GraphMode::Depth
                 | GraphMode::SlicePolar => {}
@@ -3480,10 +5019,16 @@ impl Graph {
                         }
                     }
                 }
-                GraphMode::DomainColoring | GraphMode::Depth | GraphMode::Flatten => {}
+                GraphMode::DomainColoring
+                | GraphMode::Heatmap
+                | GraphMode::RiemannSphere
+                | GraphMode::Conformal
+                | GraphMode::Depth
+                | GraphMode::Flatten => {}
             },
             GraphType::Point(p) => match self.graph_mode {
                 GraphMode::DomainColoring //TODO fix dc
+                | GraphMode::Heatmap
                 | GraphMode::Flatten
                 | GraphMode::Normal
                 | GraphMode::Slice => {
@@ -3504,10 +5049,334 @@ impl Graph {
                          self.point_size)
                     }
                 }
-                GraphMode::Depth => {}
+                GraphMode::Depth | GraphMode::RiemannSphere | GraphMode::Conformal => {}
             },
         }
     }
+    #[allow(clippy::too_many_arguments)]
+    fn plot_surface(
+        &self,
+        data: &[Vec<Complex>],
+        start_x: f64,
+        start_y: f64,
+        end_x: f64,
+        end_y: f64,
+        connect: &[(usize, usize)],
+        buffer: &mut Option<Vec<(f32, Draw, Color)>>,
+        painter: &mut Painter,
+    ) {
+        let Some(len) = data.first().map(|d| d.len().isqrt()) else {
+            return;
+        };
+        if len < 2 || data.iter().any(|d| d.len() != len * len) {
+            return;
+        }
+        let imag = matches!(self.show, Show::Imag);
+        let get = |s: usize, i: usize| {
+            let (a, b) = data[s][i].to_options();
+            let (a, b) = (a.unwrap_or(0.0), b.unwrap_or(0.0));
+            if imag { (b, a) } else { (a, b) }
+        };
+        let (min, max) = data
+            .iter()
+            .enumerate()
+            .flat_map(|(s, d)| (0..d.len()).map(move |i| (s, i)))
+            .map(|(s, i)| get(s, i).1)
+            .filter(|c| c.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), c| {
+                (a.min(c), b.max(c))
+            });
+        let sheets = data.len();
+        let next = |s: usize, i: usize, z: (f64, f64)| {
+            (0..sheets)
+                .filter(|t| {
+                    *t == s
+                        || connect.is_empty()
+                        || connect.contains(&(s, *t))
+                        || connect.contains(&(*t, s))
+                })
+                .min_by(|a, b| {
+                    let (a, b) = (get(*a, i), get(*b, i));
+                    (a.0 - z.0)
+                        .hypot(a.1 - z.1)
+                        .total_cmp(&(b.0 - z.0).hypot(b.1 - z.1))
+                })
+                .unwrap_or(s)
+        };
+        let mut last = vec![Vec::with_capacity(len); sheets];
+        let mut cur = vec![Vec::with_capacity(len); sheets];
+        for n in 0..len * len {
+            let (i, j) = (n % len, n / len);
+            let x =
+                (i as f64 / (len - 1) as f64 - 0.5) * (end_x - start_x) + (start_x + end_x) * 0.5;
+            let y =
+                (j as f64 / (len - 1) as f64 - 0.5) * (end_y - start_y) + (start_y + end_y) * 0.5;
+            for s in 0..sheets {
+                let z = get(s, n);
+                let a = if i == 0 {
+                    None
+                } else {
+                    cur[next(s, n - 1, z)][i - 1]
+                };
+                let b = if j == 0 {
+                    None
+                } else {
+                    last[next(s, n - len, z)][i]
+                };
+                let t = if max > min {
+                    (z.1 - min) / (max - min)
+                } else {
+                    0.5
+                };
+                let p = self.draw_point_3d(
+                    x,
+                    y,
+                    z.0,
+                    &self.colormap.to_color(t),
+                    a,
+                    b,
+                    buffer,
+                    painter,
+                );
+                cur[s].push(p);
+            }
+            if i == len - 1 {
+                for s in 0..sheets {
+                    last[s] = std::mem::replace(&mut cur[s], Vec::with_capacity(len));
+                }
+            }
+        }
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn plot_conformal(
+        &self,
+        painter: &mut Painter,
+        k: usize,
+        data: &[Complex],
+        start_x: f64,
+        start_y: f64,
+        end_x: f64,
+        end_y: f64,
+    ) {
+        let len = data.len().isqrt();
+        if len < 2 {
+            return;
+        }
+        let n = self.conformal_lines.max(1);
+        let w = self.screen.x as f32;
+        let mid = w * 0.5;
+        let mut curve = |pts: Vec<(f64, f64)>, color: &Color| {
+            let mut last: Option<(Pos, Option<Pos>)> = None;
+            for (x, y) in pts {
+                let z = interpolate(data, len, start_x, start_y, end_x, end_y, x, y)
+                    .filter(|(a, b)| a.is_finite() && b.is_finite())
+                    .map(|(a, b)| self.conformal_pos(a, b, true));
+                let cur = (self.conformal_pos(x, y, false), z);
+                if let Some(last) = last {
+                    if let Some(s) = clip_x(last.0, cur.0, 0.0, mid) {
+                        painter.line_segment(s, self.line_width, color)
+                    }
+                    if let (Some(a), Some(b)) = (last.1, cur.1)
+                        && let Some(s) = clip_x(a, b, mid, w)
+                    {
+                        painter.line_segment(s, self.line_width, color)
+                    }
+                }
+                last = Some(cur);
+            }
+        };
+        let main = &self.main_colors[k % self.main_colors.len()];
+        let alt = &self.alt_colors[k % self.alt_colors.len()];
+        if self.conformal_polar {
+            let (cx, cy) = ((start_x + end_x) * 0.5, (start_y + end_y) * 0.5);
+            let r = ((end_x - start_x) * 0.5)
+                .abs()
+                .min(((end_y - start_y) * 0.5).abs());
+            let m = 4 * len;
+            for l in 1..=n {
+                let r = r * l as f64 / n as f64;
+                curve(
+                    (0..=m)
+                        .map(|t| {
+                            let (s, c) = (t as f64 * TAU / m as f64).sin_cos();
+                            (cx + r * c, cy + r * s)
+                        })
+                        .collect(),
+                    main,
+                );
+            }
+            for l in 0..2 * n {
+                let (s, c) = (l as f64 * PI / n as f64).sin_cos();
+                curve(
+                    (0..len)
+                        .map(|t| {
+                            let r = r * t as f64 / (len - 1) as f64;
+                            (cx + r * c, cy + r * s)
+                        })
+                        .collect(),
+                    alt,
+                );
+            }
+        } else {
+            for l in 0..=n {
+                let t = l as f64 / n as f64;
+                let y = start_y + (end_y - start_y) * t;
+                let x = start_x + (end_x - start_x) * t;
+                curve(
+                    (0..len)
+                        .map(|i| {
+                            let x = start_x + (end_x - start_x) * i as f64 / (len - 1) as f64;
+                            (x, y)
+                        })
+                        .collect(),
+                    main,
+                );
+                curve(
+                    (0..len)
+                        .map(|j| {
+                            let y = start_y + (end_y - start_y) * j as f64 / (len - 1) as f64;
+                            (x, y)
+                        })
+                        .collect(),
+                    alt,
+                );
+            }
+        }
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn plot_sphere(
+        &self,
+        data: &[Complex],
+        start_x: f64,
+        start_y: f64,
+        end_x: f64,
+        end_y: f64,
+        inv: bool,
+        buffer: &mut Option<Vec<(f32, Draw, Color)>>,
+        painter: &mut Painter,
+    ) {
+        let len = data.len().isqrt();
+        if len < 2 {
+            return;
+        }
+        let r = (self.bound.y - self.bound.x) * 0.5;
+        let o = (self.bound.y + self.bound.x) * 0.5;
+        let mut last = Vec::with_capacity(len);
+        let mut cur = Vec::with_capacity(len);
+        for (i, z) in data.iter().enumerate() {
+            let (i, j) = (i % len, i / len);
+            let x =
+                (i as f64 / (len - 1) as f64 - 0.5) * (end_x - start_x) + (start_x + end_x) * 0.5;
+            let y =
+                (j as f64 / (len - 1) as f64 - 0.5) * (end_y - start_y) + (start_y + end_y) * 0.5;
+            let n = x * x + y * y;
+            let p = if n <= 1.1 {
+                let d = 1.0 + n;
+                let (a, b, c) = if inv {
+                    (2.0 * x / d, -2.0 * y / d, (1.0 - n) / d)
+                } else {
+                    (2.0 * x / d, 2.0 * y / d, (n - 1.0) / d)
+                };
+                self.draw_point_3d(
+                    o + r * a,
+                    o + r * b,
+                    o + r * c,
+                    &val2color(self.get_color(z)),
+                    if i == 0 { None } else { cur[i - 1] },
+                    if j == 0 { None } else { last[i] },
+                    buffer,
+                    painter,
+                )
+            } else {
+                None
+            };
+            cur.push(p);
+            if i == len - 1 {
+                last = std::mem::replace(&mut cur, Vec::with_capacity(len));
+            }
+        }
+    }
+    fn get_contours(
+        &self,
+        data: &GraphType,
+        lenx: usize,
+        leny: usize,
+        contours: &mut Vec<(Vec2, Vec2)>,
+    ) {
+        match data {
+            GraphType::List(a) => a
+                .iter()
+                .for_each(|data| self.get_contours(data, lenx, leny, contours)),
+            GraphType::Width3D(data, sx, sy, ex, ey)
+                if lenx > 1 && leny > 1 && data.len() == lenx * leny =>
+            {
+                let coord = |i: usize, j: usize, (x, y): (f64, f64)| {
+                    Vec2::new(
+                        sx + (i as f64 + x) / (lenx - 1) as f64 * (ex - sx),
+                        sy + (j as f64 + y) / (leny - 1) as f64 * (ey - sy),
+                    )
+                };
+                let cell = |v: &[f64], i: usize, j: usize| {
+                    [
+                        v[i + lenx * j],
+                        v[i + 1 + lenx * j],
+                        v[i + 1 + lenx * (j + 1)],
+                        v[i + lenx * (j + 1)],
+                    ]
+                };
+                if let Some(s) = self.contour_abs
+                    && s > 0.0
+                {
+                    let val = data
+                        .iter()
+                        .map(|z| {
+                            let (x, y) = z.to_options();
+                            let abs = x.unwrap_or(0.0).hypot(y.unwrap_or(0.0));
+                            (if self.contour_log { abs.log10() } else { abs }) / s
+                        })
+                        .collect::<Vec<f64>>();
+                    for j in 0..leny - 1 {
+                        for i in 0..lenx - 1 {
+                            let v = cell(&val, i, j);
+                            if v.iter().any(|v| !v.is_finite()) {
+                                continue;
+                            }
+                            let min = v.iter().fold(f64::INFINITY, |a, b| a.min(*b));
+                            let max = v.iter().fold(f64::NEG_INFINITY, |a, b| a.max(*b));
+                            for l in (min.ceil() as isize..=max.floor() as isize).take(8) {
+                                march(v.map(|v| v - l as f64), f64::INFINITY, |a, b| {
+                                    contours.push((coord(i, j, a), coord(i, j, b)))
+                                });
+                            }
+                        }
+                    }
+                }
+                if let Some(k) = self.contour_arg
+                    && k > 0
+                {
+                    let arg = data
+                        .iter()
+                        .map(|z| {
+                            let (x, y) = z.to_options();
+                            y.unwrap_or(0.0).atan2(x.unwrap_or(0.0))
+                        })
+                        .collect::<Vec<f64>>();
+                    for n in 0..2 * k {
+                        let l = n as f64 * PI / k as f64;
+                        for j in 0..leny - 1 {
+                            for i in 0..lenx - 1 {
+                                let v = cell(&arg, i, j).map(|a| (a - l + PI).rem_euclid(TAU) - PI);
+                                march(v, PI, |a, b| {
+                                    contours.push((coord(i, j, a), coord(i, j, b)))
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
     fn get_color(&self, z: &Complex) -> [u8; 3] {
         let (x, y) = z.to_options();
         let (x, y) = (x.unwrap_or(0.0), y.unwrap_or(0.0));
@@ -3638,6 +5507,342 @@ fn hsv2rgb(hue: f64, sat: f64, val: f64) -> [u8; 3] {
         _ => rgb2val(val, p, q),
     }
 }
+fn get_zeros(data: &GraphType, lenx: usize, leny: usize, zeros: &mut Vec<(Vec2, isize)>) {
+    match data {
+        GraphType::List(a) => a.iter().for_each(|data| get_zeros(data, lenx, leny, zeros)),
+        GraphType::Width3D(data, sx, sy, ex, ey)
+            if lenx > 2 && leny > 2 && data.len() == lenx * leny =>
+        {
+            let sign = ((ex - sx) * (ey - sy)).signum() as isize;
+            zeros.extend(find_zeros(data, lenx, leny).into_iter().map(|(x, y, n)| {
+                (
+                    Vec2::new(
+                        sx + x / (lenx - 1) as f64 * (ex - sx),
+                        sy + y / (leny - 1) as f64 * (ey - sy),
+                    ),
+                    n * sign,
+                )
+            }))
+        }
+        _ => {}
+    }
+}
+///a fingerprint of the data, so analysis and fits notice when Graph.data is changed directly
+fn data_hash(data: &[GraphType]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    fn complex(z: &Complex, h: &mut std::hash::DefaultHasher) {
+        let (a, b) = z.to_options();
+        a.map(f64::to_bits).hash(h);
+        b.map(f64::to_bits).hash(h);
+    }
+    fn floats(v: &[f64], h: &mut std::hash::DefaultHasher) {
+        v.iter().for_each(|v| h.write_u64(v.to_bits()))
+    }
+    fn walk(data: &GraphType, h: &mut std::hash::DefaultHasher) {
+        std::mem::discriminant(data).hash(h);
+        match data {
+            GraphType::Width(data, a, b) => {
+                floats(&[*a, *b], h);
+                data.iter().for_each(|z| complex(z, h))
+            }
+            GraphType::Coord(data) => data.iter().for_each(|(x, z)| {
+                floats(&[*x], h);
+                complex(z, h)
+            }),
+            GraphType::Width3D(data, a, b, c, d) => {
+                floats(&[*a, *b, *c, *d], h);
+                data.iter().for_each(|z| complex(z, h))
+            }
+            GraphType::Coord3D(data) => data.iter().for_each(|(x, y, z)| {
+                floats(&[*x, *y], h);
+                complex(z, h)
+            }),
+            GraphType::Constant(z, b) => {
+                complex(z, h);
+                b.hash(h)
+            }
+            GraphType::Point(p) => floats(&[p.x, p.y], h),
+            GraphType::List(a) => {
+                a.len().hash(h);
+                a.iter().for_each(|data| walk(data, h))
+            }
+            GraphType::Surface(data, a, b, c, d, pairs) => {
+                floats(&[*a, *b, *c, *d], h);
+                data.iter().for_each(|data| {
+                    data.len().hash(h);
+                    data.iter().for_each(|z| complex(z, h))
+                });
+                pairs.hash(h)
+            }
+            GraphType::None => {}
+        }
+    }
+    let mut h = std::hash::DefaultHasher::new();
+    data.len().hash(&mut h);
+    data.iter().for_each(|data| walk(data, &mut h));
+    h.finish()
+}
+fn get_seams(data: &GraphType, cuts: &mut Vec<(Vec<bool>, Vec<bool>)>) {
+    match data {
+        GraphType::List(a) => a.iter().for_each(|data| get_seams(data, cuts)),
+        GraphType::Width3D(data, _, _, _, _) => {
+            let len = data.len().isqrt();
+            cuts.push(branch_cuts(data, len, len))
+        }
+        _ => {}
+    }
+}
+fn get_branch_cuts(data: &GraphType, lenx: usize, leny: usize, cuts: &mut Vec<(Vec2, Vec2)>) {
+    match data {
+        GraphType::List(a) => a
+            .iter()
+            .for_each(|data| get_branch_cuts(data, lenx, leny, cuts)),
+        GraphType::Width3D(data, sx, sy, ex, ey) => {
+            let (cutx, cuty) = branch_cuts(data, lenx, leny);
+            let coord = |x: f64, y: f64| {
+                Vec2::new(
+                    sx + x / (lenx - 1) as f64 * (ex - sx),
+                    sy + y / (leny - 1) as f64 * (ey - sy),
+                )
+            };
+            for (k, (x, y)) in cutx.into_iter().zip(cuty).enumerate() {
+                let (i, j) = ((k % lenx) as f64, (k / lenx) as f64);
+                if x {
+                    cuts.push((coord(i + 0.5, j - 0.5), coord(i + 0.5, j + 0.5)))
+                }
+                if y {
+                    cuts.push((coord(i - 0.5, j + 0.5), coord(i + 0.5, j + 0.5)))
+                }
+            }
+        }
+        _ => {}
+    }
+}
+///for the point i,j of a len*len grid, returns weather the edges to the previous point
+///in x and y cross a branch cut, and weather they run along one
+fn seams(
+    cuts: Option<&(Vec<bool>, Vec<bool>)>,
+    len: usize,
+    i: usize,
+    j: usize,
+) -> (bool, bool, bool, bool) {
+    let Some((cutx, cuty)) = cuts else {
+        return (false, false, false, false);
+    };
+    let k = i + len * j;
+    let cut_a = i > 0 && cutx[k - 1];
+    let cut_b = j > 0 && cuty[k - len];
+    let seam_a =
+        i > 0 && ((cuty[k - 1] && cuty[k]) || (j > 0 && cuty[k - 1 - len] && cuty[k - len]));
+    let seam_b =
+        j > 0 && ((cutx[k - len] && cutx[k]) || (i > 0 && cutx[k - 1 - len] && cutx[k - 1]));
+    (cut_a, cut_b, seam_a, seam_b)
+}
+fn find_zeros(data: &[Complex], lenx: usize, leny: usize) -> Vec<(f64, f64, isize)> {
+    let arg = data
+        .iter()
+        .map(|z| {
+            let (x, y) = z.to_options();
+            y.unwrap_or(0.0).atan2(x.unwrap_or(0.0))
+        })
+        .collect::<Vec<f64>>();
+    let ind = |i: usize, j: usize| i + lenx * j;
+    let mut found: Vec<(f64, f64, f64, isize)> = Vec::new();
+    'outer: for j in 0..leny.saturating_sub(2) {
+        for i in 0..lenx.saturating_sub(2) {
+            let ring = [
+                ind(i, j),
+                ind(i + 1, j),
+                ind(i + 2, j),
+                ind(i + 2, j + 1),
+                ind(i + 2, j + 2),
+                ind(i + 1, j + 2),
+                ind(i, j + 2),
+                ind(i, j + 1),
+            ];
+            if ring.iter().any(|k| arg[*k].is_nan()) {
+                continue;
+            }
+            let w = (0..8)
+                .map(|a| (arg[ring[(a + 1) % 8]] - arg[ring[a]] + PI).rem_euclid(TAU) - PI)
+                .sum::<f64>();
+            let n = (w / TAU).round() as isize;
+            if n == 0 {
+                continue;
+            }
+            let (x, y) = (i as f64 + 1.0, j as f64 + 1.0);
+            if let Some(f) = found
+                .iter_mut()
+                .find(|(a, b, c, _)| (a / c - x).abs() < 2.5 && (b / c - y).abs() < 2.5)
+            {
+                f.0 += x;
+                f.1 += y;
+                f.2 += 1.0;
+                if n.abs() > f.3.abs() {
+                    f.3 = n
+                }
+            } else if found.len() < 256 {
+                found.push((x, y, 1.0, n));
+            } else {
+                break 'outer;
+            }
+        }
+    }
+    found
+        .into_iter()
+        .map(|(x, y, c, n)| (x / c, y / c, n))
+        .collect()
+}
+///for each point of a lenx*leny grid, weather the edges to the next point in x and y cross a
+///branch cut, where the argument jumps well past the change between neighbouring points,
+///as it does across a cut where a multivalued function wraps by 2pi, away from zeros and poles
+fn branch_cuts(data: &[Complex], lenx: usize, leny: usize) -> (Vec<bool>, Vec<bool>) {
+    let mut cutx = vec![false; data.len()];
+    let mut cuty = vec![false; data.len()];
+    if lenx < 3 || leny < 3 || data.len() != lenx * leny {
+        return (cutx, cuty);
+    }
+    let arg = data
+        .iter()
+        .map(|z| {
+            let (x, y) = z.to_options();
+            y.unwrap_or(0.0).atan2(x.unwrap_or(0.0))
+        })
+        .collect::<Vec<f64>>();
+    let dist = |a: usize, b: usize| ((arg[b] - arg[a] + PI).rem_euclid(TAU) - PI).abs();
+    let zeros = find_zeros(data, lenx, leny);
+    let near = |i: usize, j: usize| {
+        zeros
+            .iter()
+            .any(|(x, y, _)| (x - i as f64).abs() < 2.5 && (y - j as f64).abs() < 2.5)
+    };
+    let jump = |d: f64, n: [Option<f64>; 2]| {
+        let m = n.into_iter().flatten().fold(0.0, f64::max);
+        d > PI / 4.0 && d > 4.0 * m
+    };
+    for j in 0..leny {
+        for i in 0..lenx {
+            let k = i + lenx * j;
+            if i + 1 < lenx && !near(i, j) {
+                let n = [
+                    (i > 0).then(|| dist(k - 1, k)),
+                    (i + 2 < lenx).then(|| dist(k + 1, k + 2)),
+                ];
+                cutx[k] = jump(dist(k, k + 1), n);
+            }
+            if j + 1 < leny && !near(i, j) {
+                let n = [
+                    (j > 0).then(|| dist(k - lenx, k)),
+                    (j + 2 < leny).then(|| dist(k + lenx, k + 2 * lenx)),
+                ];
+                cuty[k] = jump(dist(k, k + lenx), n);
+            }
+        }
+    }
+    (cutx, cuty)
+}
+fn march<F>(d: [f64; 4], max: f64, mut f: F)
+where
+    F: FnMut((f64, f64), (f64, f64)),
+{
+    const C: [(f64, f64); 4] = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+    let mut p = [(0.0, 0.0); 4];
+    let mut n = 0;
+    for a in 0..4 {
+        let b = (a + 1) % 4;
+        if (d[a] >= 0.0) != (d[b] >= 0.0) && (d[a] - d[b]).abs() < max {
+            let t = d[a] / (d[a] - d[b]);
+            p[n] = (
+                C[a].0 + t * (C[b].0 - C[a].0),
+                C[a].1 + t * (C[b].1 - C[a].1),
+            );
+            n += 1;
+        }
+    }
+    if n >= 2 {
+        f(p[0], p[1])
+    }
+    if n == 4 {
+        f(p[2], p[3])
+    }
+}
+fn heatmap_bound(data: &[GraphType]) -> Vec2 {
+    fn bound(data: &GraphType, b: &mut Vec2) {
+        match data {
+            GraphType::Width3D(data, _, _, _, _) => {
+                for z in data.iter().filter_map(|z| z.to_options().0) {
+                    if z.is_finite() {
+                        b.x = b.x.min(z);
+                        b.y = b.y.max(z);
+                    }
+                }
+            }
+            GraphType::List(a) => a.iter().for_each(|data| bound(data, b)),
+            _ => {}
+        }
+    }
+    let mut b = Vec2::new(f64::INFINITY, f64::NEG_INFINITY);
+    data.iter().for_each(|data| bound(data, &mut b));
+    if b.x > b.y {
+        Vec2::new(0.0, 1.0)
+    } else if b.x == b.y {
+        Vec2::new(b.x - 0.5, b.y + 0.5)
+    } else {
+        b
+    }
+}
+#[allow(clippy::too_many_arguments)]
+fn interpolate(
+    data: &[Complex],
+    len: usize,
+    start_x: f64,
+    start_y: f64,
+    end_x: f64,
+    end_y: f64,
+    x: f64,
+    y: f64,
+) -> Option<(f64, f64)> {
+    let m = (len - 1) as f64;
+    let fx = (x - start_x) / (end_x - start_x) * m;
+    let fy = (y - start_y) / (end_y - start_y) * m;
+    if !(0.0..=m).contains(&fx) || !(0.0..=m).contains(&fy) {
+        return None;
+    }
+    let (i, j) = ((fx as usize).min(len - 2), (fy as usize).min(len - 2));
+    let (tx, ty) = (fx - i as f64, fy - j as f64);
+    let get = |i: usize, j: usize| {
+        let (a, b) = data[i + len * j].to_options();
+        (a.unwrap_or(0.0), b.unwrap_or(0.0))
+    };
+    let (a, b, c, d) = (get(i, j), get(i + 1, j), get(i, j + 1), get(i + 1, j + 1));
+    Some((
+        (a.0 * (1.0 - tx) + b.0 * tx) * (1.0 - ty) + (c.0 * (1.0 - tx) + d.0 * tx) * ty,
+        (a.1 * (1.0 - tx) + b.1 * tx) * (1.0 - ty) + (c.1 * (1.0 - tx) + d.1 * tx) * ty,
+    ))
+}
+fn clip_x(a: Pos, b: Pos, min: f32, max: f32) -> Option<[Pos; 2]> {
+    if !(a.x.is_finite() && a.y.is_finite() && b.x.is_finite() && b.y.is_finite()) {
+        return None;
+    }
+    let (mut a, mut b) = if a.x <= b.x { (a, b) } else { (b, a) };
+    if b.x < min || a.x > max {
+        return None;
+    }
+    if a.x < min {
+        a = Pos::new(min, a.y + (b.y - a.y) * (min - a.x) / (b.x - a.x));
+    }
+    if b.x > max {
+        b = Pos::new(max, a.y + (b.y - a.y) * (max - a.x) / (b.x - a.x));
+    }
+    Some([a, b])
+}
+fn val2color([r, g, b]: [u8; 3]) -> Color {
+    if cfg!(all(feature = "tiny-skia", not(target_arch = "wasm32"))) {
+        Color::new(b, g, r)
+    } else {
+        Color::new(r, g, b)
+    }
+}
 fn rgb2val(r: f64, g: f64, b: f64) -> [u8; 3] {
     if cfg!(all(feature = "tiny-skia", not(target_arch = "wasm32"))) {
         [(255.0 * b) as u8, (255.0 * g) as u8, (255.0 * r) as u8]
@@ -3770,14 +5975,221 @@ fn point(
     }
 }
 #[cfg(feature = "serde")]
-pub(crate) fn update_saves(fd: &mut Vec<String>, n: &[(String, usize, String)]) {
-    *fd = n
-        .iter()
-        .map(|(a, c, d)| {
-            let s = |s: &str| base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(s);
-            format!("{}@{}@{}", s(a), s(c.to_string().as_str()), d)
+pub(crate) fn update_saves(fd: &mut Vec<String>, n: &[SaveEntry], text: bool) {
+    *fd = n.iter().map(|e| format_save(e, text)).collect();
+}
+#[cfg(feature = "serde")]
+///averages the centered square of the plot at offset of size screen into a
+///THUMBNAIL by THUMBNAIL rgb image, given the frame size and the rgb color of each pixel
+fn thumbnail<F>(pixel: F, offset: Pos, screen: Vec2, (fw, fh): (usize, usize)) -> Vec<u8>
+where
+    F: Fn(usize, usize) -> [u8; 3],
+{
+    let (x, y) = (offset.x as usize, offset.y as usize);
+    let (w, h) = (screen.x as usize, screen.y as usize);
+    let (w, h) = (w.min(fw.saturating_sub(x)), h.min(fh.saturating_sub(y)));
+    let s = w.min(h);
+    if s < THUMBNAIL {
+        return Vec::new();
+    }
+    let (x, y) = (x + (w - s) / 2, y + (h - s) / 2);
+    let mut out = Vec::with_capacity(THUMBNAIL * THUMBNAIL * 3);
+    for j in 0..THUMBNAIL {
+        let (y0, y1) = (y + j * s / THUMBNAIL, y + (j + 1) * s / THUMBNAIL);
+        for i in 0..THUMBNAIL {
+            let (x0, x1) = (x + i * s / THUMBNAIL, x + (i + 1) * s / THUMBNAIL);
+            let mut sum = [0; 3];
+            for py in y0..y1 {
+                for px in x0..x1 {
+                    for (s, c) in sum.iter_mut().zip(pixel(px, py)) {
+                        *s += c as usize
+                    }
+                }
+            }
+            let n = (x1 - x0) * (y1 - y0);
+            out.extend(sum.map(|s| (s / n) as u8));
+        }
+    }
+    out
+}
+#[cfg(feature = "serde")]
+///a unix time as a utc date and time like 2024-03-09 14:05
+pub(crate) fn format_time(secs: u64) -> String {
+    let (h, m) = (secs % 86400 / 3600, secs % 3600 / 60);
+    let z = (secs / 86400) as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let mo = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(mo <= 2);
+    format!("{y}-{mo:02}-{d:02} {h:02}:{m:02}")
+}
+#[cfg(feature = "serde")]
+///if a save file is human readable json rather than compressed bitcode, chosen by its extension
+pub(crate) fn is_text_save(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"))
+}
+#[cfg(feature = "serde")]
+///serialized graph, along with its uncompressed length for the bitcode format
+pub(crate) fn encode_save(graph: &Graph, text: bool) -> Result<(usize, String), RuplError> {
+    if text {
+        Ok((
+            0,
+            serde_json::to_string(graph).map_err(|e| RuplError::Decode(e.to_string()))?,
+        ))
+    } else {
+        let seri = bitcode::serialize(graph).map_err(|e| RuplError::Decode(e.to_string()))?;
+        let comp =
+            zstd::bulk::compress(&seri, 22).map_err(|e| RuplError::Decompress(e.to_string()))?;
+        Ok((
+            seri.len(),
+            base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(&comp),
+        ))
+    }
+}
+#[cfg(feature = "serde")]
+///decodes a graph saved in the given version, migrating it to the current layout
+pub(crate) fn decode_save(l: usize, s: &str, v: u32, text: bool) -> Result<Graph, RuplError> {
+    check_version(v)?;
+    if text {
+        serde_json::from_str(s).map_err(|e| RuplError::Decode(e.to_string()))
+    } else {
+        let s = base64::prelude::BASE64_URL_SAFE_NO_PAD
+            .decode(s)
+            .map_err(|e| RuplError::Decode(e.to_string()))?;
+        let data = decompress(&s, l)?;
+        decode_graph(v, &data)
+    }
+}
+#[cfg(feature = "serde")]
+///one save entry, v2@name@len@data@meta in base64 for bitcode,
+///or a json object with the version, name, meta and graph,
+///entries older than version 2 are written without meta, unreadable entries as they were read
+pub(crate) fn format_save(e: &SaveEntry, text: bool) -> String {
+    if let Some(raw) = &e.raw {
+        return raw.clone();
+    }
+    let b = |s: &[u8]| base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(s);
+    if text {
+        let mut v = serde_json::json!({
+            "version": e.version,
+            "name": e.name,
+            "graph": serde_json::from_str::<serde_json::Value>(&e.data).unwrap_or_default(),
+        });
+        if e.version >= 2 {
+            v["meta"] = serde_json::json!({
+                "time": e.meta.time,
+                "graph_mode": e.meta.graph_mode,
+                "names": e.meta.names,
+                "thumbnail": b(&e.meta.thumbnail),
+            });
+        }
+        v.to_string()
+    } else {
+        let n = format!(
+            "{}@{}@{}",
+            b(e.name.as_bytes()),
+            b(e.len.to_string().as_bytes()),
+            e.data
+        );
+        match e.version {
+            0 => n,
+            1 => format!("v1@{n}"),
+            v => format!(
+                "v{v}@{n}@{}",
+                b(&bitcode::serialize(&e.meta).unwrap_or_default())
+            ),
+        }
+    }
+}
+#[cfg(feature = "serde")]
+///parses a save entry, entries without a version header are version 0
+pub(crate) fn parse_save(s: &str, text: bool) -> Result<SaveEntry, RuplError> {
+    let decode = |e: &dyn std::fmt::Display| RuplError::Decode(e.to_string());
+    let b = |s: &str| base64::prelude::BASE64_URL_SAFE_NO_PAD.decode(s);
+    if text {
+        let v = serde_json::from_str::<serde_json::Value>(s).map_err(|e| decode(&e))?;
+        let (Some(name), false) = (v["name"].as_str(), v["graph"].is_null()) else {
+            return Err(decode(&"save entry without a name or graph"));
+        };
+        let m = &v["meta"];
+        Ok(SaveEntry {
+            name: name.to_string(),
+            len: 0,
+            data: v["graph"].to_string(),
+            version: v["version"].as_u64().unwrap_or(0) as u32,
+            meta: SaveMeta {
+                time: m["time"].as_u64().unwrap_or(0),
+                graph_mode: serde_json::from_value(m["graph_mode"].clone()).unwrap_or_default(),
+                names: serde_json::from_value(m["names"].clone()).unwrap_or_default(),
+                thumbnail: m["thumbnail"]
+                    .as_str()
+                    .and_then(|s| b(s).ok())
+                    .unwrap_or_default(),
+            },
+            raw: None,
+        })
+    } else {
+        let (version, s) = split_version(s);
+        let (s, meta) = match s.rsplit_once('@') {
+            Some((s, meta)) if version >= 2 => (s, Some(meta)),
+            _ => (s, None),
+        };
+        let [data, len, name] = s.rsplitn(3, '@').collect::<Vec<&str>>()[..] else {
+            return Err(decode(&"save entry without a name or length"));
+        };
+        let s = |s: &str| String::from_utf8(b(s).map_err(|e| decode(&e))?).map_err(|e| decode(&e));
+        Ok(SaveEntry {
+            name: s(name)?,
+            len: s(len)?.parse().map_err(|e| decode(&e))?,
+            data: data.to_string(),
+            version,
+            meta: match meta {
+                Some(m) => {
+                    bitcode::deserialize(&b(m).map_err(|e| decode(&e))?).map_err(|e| decode(&e))?
+                }
+                None => SaveMeta::default(),
+            },
+            raw: None,
         })
-        .collect();
+    }
+}
+#[cfg(feature = "serde")]
+///save entries of a save file, one per line for bitcode, or one per element of a json array
+pub(crate) fn read_saves(path: &str, text: bool) -> Result<Vec<String>, RuplError> {
+    let file = std::fs::read_to_string(path)?;
+    if text {
+        if file.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_str::<Vec<serde_json::Value>>(&file)
+            .map_err(|e| RuplError::Decode(e.to_string()))?
+            .iter()
+            .map(|v| v.to_string())
+            .collect())
+    } else {
+        Ok(file.lines().map(str::to_string).collect())
+    }
+}
+#[cfg(feature = "serde")]
+pub(crate) fn write_saves(path: &str, saves: &[String], text: bool) -> Result<(), RuplError> {
+    let file = if text {
+        serde_json::to_string_pretty(
+            &saves
+                .iter()
+                .map(|s| serde_json::from_str::<serde_json::Value>(s).unwrap_or_default())
+                .collect::<Vec<serde_json::Value>>(),
+        )
+        .map_err(|e| RuplError::Decode(e.to_string()))?
+    } else {
+        saves.join("\n")
+    };
+    Ok(std::fs::write(path, file)?)
 }
 #[cfg(feature = "serde")]
 impl Drop for Graph {
@@ -3801,3 +6213,19 @@ pub fn get_surface(width: i32, height: i32) -> skia_safe::Surface {
     )
     .unwrap()
 }
+///linearly interpolates sorted samples at x, extrapolating from the end segments
+pub(crate) fn lerp(pts: &[(f64, f64)], x: f64) -> f64 {
+    match pts {
+        [] => f64::NAN,
+        [(_, y)] => *y,
+        _ => {
+            let i = pts.partition_point(|p| p.0 < x).clamp(1, pts.len() - 1);
+            let ((x0, y0), (x1, y1)) = (pts[i - 1], pts[i]);
+            if x1 == x0 {
+                y1
+            } else {
+                y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use crate::types::*;
+use serde::Deserialize;
+///version written in the header of save entries and GraphTiny strings,
+///bumped whenever the serialized layout of Graph, GraphTiny or a save entry changes,
+///
+///version 2 adds SaveMeta to save entries, version 3 adds data to GraphTiny
+pub(crate) const SAVE_VERSION: u32 = 3;
+///layout of Graph in unversioned saves
+#[derive(Deserialize)]
+pub(crate) struct GraphV0 {
+    names: Vec<Name>,
+    name_updated: Option<usize>,
+    font_size: f32,
+    font_width: f32,
+    line_width: f32,
+    point_size: f32,
+    #[cfg(feature = "skia")]
+    image_format: crate::ui::ImageFormat,
+    fast_3d: bool,
+    fast_3d_move: bool,
+    reduced_move: bool,
+    bound: Vec2,
+    is_complex: bool,
+    offset3d: Vec3,
+    offset: Vec2,
+    angle: Vec2,
+    ignore_bounds: bool,
+    zoom: Vec2,
+    zoom_3d: Vec3,
+    slice: isize,
+    var: Vec2,
+    log_scale: bool,
+    box_size: f64,
+    domain_alternate: bool,
+    screen: Vec2,
+    screen_offset: Vec2,
+    delta: f64,
+    show: Show,
+    anti_alias: bool,
+    color_depth: DepthColor,
+    show_box: bool,
+    main_colors: Vec<Color>,
+    alt_colors: Vec<Color>,
+    axis_color: Color,
+    blacklist_graphs: Vec<usize>,
+    axis_color_light: Color,
+    background_color: Color,
+    text_color: Color,
+    mouse_position: Option<Vec2>,
+    mouse_moved: bool,
+    disable_lines: bool,
+    disable_axis: bool,
+    disable_coord: bool,
+    view_x: bool,
+    graph_mode: GraphMode,
+    is_3d: bool,
+    is_3d_data: bool,
+    angle_type: Angle,
+    last_interact: Option<Vec2>,
+    last_right_interact: Option<Vec2>,
+    recalculate: bool,
+    name_modified: bool,
+    lines: Lines,
+    ruler_pos: Option<Vec2>,
+    prec: f64,
+    mouse_held: bool,
+    mult: f64,
+    line_major: usize,
+    line_minor: usize,
+    draw_offset: Pos,
+    cos_phi: f64,
+    sin_phi: f64,
+    cos_theta: f64,
+    sin_theta: f64,
+    select: Option<(usize, usize, Option<bool>)>,
+    text_box: Option<(usize, usize)>,
+    side_slider: Option<usize>,
+    side_drag: Option<(usize, Option<usize>)>,
+    last_multi: bool,
+    side_bar_width: f64,
+    history: Vec<Change>,
+    history_pos: usize,
+    text_scroll_pos: (usize, usize),
+    only_real: bool,
+    menu: MenuV0,
+    side_height: f32,
+    min_side_width: f64,
+    min_screen_width: f64,
+    target_side_ratio: f64,
+    bracket_color: Vec<Color>,
+    select_color: Color,
+    #[cfg(feature = "arboard")]
+    wait_frame: bool,
+    save_file: String,
+    save_num: Option<usize>,
+}
+///layout of Menu in unversioned saves, before the keybinds menu
+#[derive(Deserialize)]
+pub(crate) enum MenuV0 {
+    Normal,
+    Side,
+    Settings,
+    Load,
+}
+impl From<GraphV0> for Graph {
+    fn from(v: GraphV0) -> Self {
+        let mut graph = Graph::default();
+        graph.names = v.names;
+        graph.name_updated = v.name_updated;
+        graph.font_size = v.font_size;
+        graph.font_width = v.font_width;
+        graph.line_width = v.line_width;
+        graph.point_size = v.point_size;
+        #[cfg(feature = "skia")]
+        {
+            graph.image_format = v.image_format;
+        }
+        graph.fast_3d = v.fast_3d;
+        graph.fast_3d_move = v.fast_3d_move;
+        graph.reduced_move = v.reduced_move;
+        graph.bound = v.bound;
+        graph.is_complex = v.is_complex;
+        graph.offset3d = v.offset3d;
+        graph.offset = v.offset;
+        graph.angle = v.angle;
+        graph.ignore_bounds = v.ignore_bounds;
+        graph.zoom = v.zoom;
+        graph.zoom_3d = v.zoom_3d;
+        graph.slice = v.slice;
+        graph.var = v.var;
+        graph.log_scale = v.log_scale;
+        graph.box_size = v.box_size;
+        graph.domain_alternate = v.domain_alternate;
+        graph.screen = v.screen;
+        graph.screen_offset = v.screen_offset;
+        graph.delta = v.delta;
+        graph.show = v.show;
+        graph.anti_alias = v.anti_alias;
+        graph.color_depth = v.color_depth;
+        graph.show_box = v.show_box;
+        graph.main_colors = v.main_colors;
+        graph.alt_colors = v.alt_colors;
+        graph.axis_color = v.axis_color;
+        graph.blacklist_graphs = v.blacklist_graphs;
+        graph.axis_color_light = v.axis_color_light;
+        graph.background_color = v.background_color;
+        graph.text_color = v.text_color;
+        graph.mouse_position = v.mouse_position;
+        graph.mouse_moved = v.mouse_moved;
+        graph.disable_lines = v.disable_lines;
+        graph.disable_axis = v.disable_axis;
+        graph.disable_coord = v.disable_coord;
+        graph.view_x = v.view_x;
+        graph.graph_mode = v.graph_mode;
+        graph.is_3d = v.is_3d;
+        graph.is_3d_data = v.is_3d_data;
+        graph.angle_type = v.angle_type;
+        graph.last_interact = v.last_interact;
+        graph.last_right_interact = v.last_right_interact;
+        graph.recalculate = v.recalculate;
+        graph.name_modified = v.name_modified;
+        graph.lines = v.lines;
+        graph.ruler_pos = v.ruler_pos;
+        graph.prec = v.prec;
+        graph.mouse_held = v.mouse_held;
+        graph.mult = v.mult;
+        graph.line_major = v.line_major;
+        graph.line_minor = v.line_minor;
+        graph.draw_offset = v.draw_offset;
+        graph.cos_phi = v.cos_phi;
+        graph.sin_phi = v.sin_phi;
+        graph.cos_theta = v.cos_theta;
+        graph.sin_theta = v.sin_theta;
+        graph.select = v.select;
+        graph.text_box = v.text_box;
+        graph.side_slider = v.side_slider;
+        graph.side_drag = v.side_drag;
+        graph.last_multi = v.last_multi;
+        graph.side_bar_width = v.side_bar_width;
+        graph.history = v.history;
+        graph.history_pos = v.history_pos;
+        graph.text_scroll_pos = v.text_scroll_pos;
+        graph.only_real = v.only_real;
+        graph.menu = match v.menu {
+            MenuV0::Normal => Menu::Normal,
+            MenuV0::Side => Menu::Side,
+            MenuV0::Settings => Menu::Settings,
+            MenuV0::Load => Menu::Load,
+        };
+        graph.side_height = v.side_height;
+        graph.min_side_width = v.min_side_width;
+        graph.min_screen_width = v.min_screen_width;
+        graph.target_side_ratio = v.target_side_ratio;
+        graph.bracket_color = v.bracket_color;
+        graph.select_color = v.select_color;
+        #[cfg(feature = "arboard")]
+        {
+            graph.wait_frame = v.wait_frame;
+        }
+        graph.save_file = v.save_file;
+        graph.save_num = v.save_num;
+        graph
+    }
+}
+///decodes a graph serialized with bitcode in the layout of the given version,
+///migrating older layouts up to the current one
+pub(crate) fn decode_graph(version: u32, data: &[u8]) -> Result<Graph, RuplError> {
+    match version {
+        0 => bitcode::deserialize::<GraphV0>(data)
+            .map(Graph::from)
+            .map_err(|e| RuplError::Decode(e.to_string())),
+        1..=SAVE_VERSION => {
+            bitcode::deserialize(data).map_err(|e| RuplError::Decode(e.to_string()))
+        }
+        v => Err(RuplError::Version(v)),
+    }
+}
+///layout of GraphTiny before version 3, without data
+#[derive(Deserialize)]
+pub(crate) struct GraphTinyV2 {
+    names: Vec<Name>,
+    bound: (f32, f32),
+    prec: f32,
+    is_complex: bool,
+    offset3d: Option<(f32, f32, f32)>,
+    offset: Option<(f32, f32)>,
+    zoom: Option<(f32, f32)>,
+    zoom_3d: Option<(f32, f32, f32)>,
+    slice: i8,
+    var: (f32, f32),
+    log_scale: bool,
+    domain_alternate: bool,
+    color_depth: DepthColor,
+    blacklist_graphs: Vec<u8>,
+    view_x: bool,
+    graph_mode: GraphMode,
+    only_real: bool,
+}
+impl From<GraphTinyV2> for GraphTiny {
+    fn from(v: GraphTinyV2) -> Self {
+        GraphTiny {
+            names: v.names,
+            bound: v.bound,
+            prec: v.prec,
+            is_complex: v.is_complex,
+            offset3d: v.offset3d,
+            offset: v.offset,
+            zoom: v.zoom,
+            zoom_3d: v.zoom_3d,
+            slice: v.slice,
+            var: v.var,
+            log_scale: v.log_scale,
+            domain_alternate: v.domain_alternate,
+            color_depth: v.color_depth,
+            blacklist_graphs: v.blacklist_graphs,
+            view_x: v.view_x,
+            graph_mode: v.graph_mode,
+            only_real: v.only_real,
+            data: Vec::new(),
+        }
+    }
+}
+///decodes a GraphTiny serialized with bitcode in the layout of the given version
+pub(crate) fn decode_tiny(version: u32, data: &[u8]) -> Result<GraphTiny, RuplError> {
+    match version {
+        0..=2 => bitcode::deserialize::<GraphTinyV2>(data)
+            .map(GraphTiny::from)
+            .map_err(|e| RuplError::Decode(e.to_string())),
+        SAVE_VERSION => bitcode::deserialize(data).map_err(|e| RuplError::Decode(e.to_string())),
+        v => Err(RuplError::Version(v)),
+    }
+}
+///largest decompressed length accepted from a save entry or GraphTiny string,
+///so a corrupted or forged length can not allocate unbounded memory
+pub(crate) const MAX_DECOMPRESSED: usize = 1 << 26;
+///decompresses zstd data of the given length, rejecting lengths past MAX_DECOMPRESSED
+pub(crate) fn decompress(data: &[u8], len: usize) -> Result<Vec<u8>, RuplError> {
+    if len > MAX_DECOMPRESSED {
+        return Err(RuplError::Decompress(format!(
+            "length {len} is larger than {MAX_DECOMPRESSED}"
+        )));
+    }
+    zstd::bulk::decompress(data, len).map_err(|e| RuplError::Decompress(e.to_string()))
+}
+///errors for saves from a newer version than this one
+pub(crate) fn check_version(version: u32) -> Result<(), RuplError> {
+    if version > SAVE_VERSION {
+        Err(RuplError::Version(version))
+    } else {
+        Ok(())
+    }
+}
+///splits a "v1@" version header from the front of a save entry, none for unversioned entries
+pub(crate) fn split_version(s: &str) -> (u32, &str) {
+    s.strip_prefix('v')
+        .and_then(|s| s.split_once('@'))
+        .and_then(|(v, s)| Some((v.parse().ok()?, s)))
+        .unwrap_or((0, s))
+}
@@ -0,0 +1,323 @@
+use crate::types::*;
+#[derive(Clone, Copy)]
+enum Kind {
+    Float(f64, f64),
+    Int(usize, usize),
+    Bool,
+    Other,
+}
+const SETTINGS: &[(&str, Kind)] = &[
+    ("line_width", Kind::Float(0.25, 16.0)),
+    ("point_size", Kind::Float(0.25, 16.0)),
+    ("font_size", Kind::Float(6.0, 48.0)),
+    ("side_height", Kind::Float(1.0, 4.0)),
+    ("line_major", Kind::Int(1, 32)),
+    ("line_minor", Kind::Int(0, 16)),
+    ("box_size", Kind::Float(0.5, 4.0)),
+    ("prec", Kind::Float(0.125, 8.0)),
+    ("mult", Kind::Float(0.125, 4.0)),
+    ("conformal_lines", Kind::Int(1, 64)),
+    ("fast_3d", Kind::Bool),
+    ("fast_3d_move", Kind::Bool),
+    ("reduced_move", Kind::Bool),
+    ("anti_alias", Kind::Bool),
+    ("show_box", Kind::Bool),
+    ("ignore_bounds", Kind::Bool),
+    ("only_real", Kind::Bool),
+    ("log_scale", Kind::Bool),
+    ("domain_alternate", Kind::Bool),
+    ("disable_lines", Kind::Bool),
+    ("disable_axis", Kind::Bool),
+    ("disable_coord", Kind::Bool),
+    ("show_contour", Kind::Bool),
+    ("contour_log", Kind::Bool),
+    ("show_zeros", Kind::Bool),
+    ("show_branch", Kind::Bool),
+    ("show_analysis", Kind::Bool),
+    ("conformal_polar", Kind::Bool),
+    ("lines", Kind::Other),
+    ("colormap", Kind::Other),
+    ("angle_type", Kind::Other),
+    ("axis_color", Kind::Other),
+    ("axis_color_light", Kind::Other),
+    ("background_color", Kind::Other),
+    ("text_color", Kind::Other),
+    ("select_color", Kind::Other),
+    ("branch_color", Kind::Other),
+    ("main_colors", Kind::Other),
+    ("alt_colors", Kind::Other),
+    ("min_side_width", Kind::Float(0.0, 1024.0)),
+    ("min_screen_width", Kind::Float(0.0, 1024.0)),
+    ("target_side_ratio", Kind::Float(0.25, 4.0)),
+];
+impl Graph {
+    ///lines shown in the settings menu, formatted as name=value
+    pub(crate) fn settings_list(&self) -> Vec<String> {
+        SETTINGS
+            .iter()
+            .map(|(name, _)| format!("{name}={}", self.get_setting(name)))
+            .collect()
+    }
+    fn get_setting(&self, name: &str) -> String {
+        let colors = |c: &[Color]| c.iter().map(|c| c.to_hex()).collect::<Vec<_>>().join(",");
+        match name {
+            "line_width" => self.line_width.to_string(),
+            "point_size" => self.point_size.to_string(),
+            "font_size" => self.font_size.to_string(),
+            "side_height" => self.side_height.to_string(),
+            "line_major" => self.line_major.to_string(),
+            "line_minor" => self.line_minor.to_string(),
+            "box_size" => self.box_size.to_string(),
+            "prec" => self.prec.to_string(),
+            "mult" => self.mult.to_string(),
+            "conformal_lines" => self.conformal_lines.to_string(),
+            "fast_3d" => self.fast_3d.to_string(),
+            "fast_3d_move" => self.fast_3d_move.to_string(),
+            "reduced_move" => self.reduced_move.to_string(),
+            "anti_alias" => self.anti_alias.to_string(),
+            "show_box" => self.show_box.to_string(),
+            "ignore_bounds" => self.ignore_bounds.to_string(),
+            "only_real" => self.only_real.to_string(),
+            "log_scale" => self.log_scale.to_string(),
+            "domain_alternate" => self.domain_alternate.to_string(),
+            "disable_lines" => self.disable_lines.to_string(),
+            "disable_axis" => self.disable_axis.to_string(),
+            "disable_coord" => self.disable_coord.to_string(),
+            "show_contour" => self.show_contour.to_string(),
+            "contour_log" => self.contour_log.to_string(),
+            "show_zeros" => self.show_zeros.to_string(),
+            "show_branch" => self.show_branch.to_string(),
+            "show_analysis" => self.show_analysis.to_string(),
+            "conformal_polar" => self.conformal_polar.to_string(),
+            "lines" => match self.lines {
+                Lines::Lines => "lines",
+                Lines::Points => "points",
+                Lines::LinesPoints => "linespoints",
+            }
+            .to_string(),
+            "colormap" => match self.colormap {
+                Colormap::Viridis => "viridis",
+                Colormap::Inferno => "inferno",
+                Colormap::Coolwarm => "coolwarm",
+                Colormap::Grayscale => "grayscale",
+            }
+            .to_string(),
+            "angle_type" => match self.angle_type {
+                Angle::Radian => "radian",
+                Angle::Degree => "degree",
+                Angle::Gradian => "gradian",
+            }
+            .to_string(),
+            "axis_color" => self.axis_color.to_hex(),
+            "axis_color_light" => self.axis_color_light.to_hex(),
+            "background_color" => self.background_color.to_hex(),
+            "text_color" => self.text_color.to_hex(),
+            "select_color" => self.select_color.to_hex(),
+            "branch_color" => self.branch_color.to_hex(),
+            "main_colors" => colors(&self.main_colors),
+            "alt_colors" => colors(&self.alt_colors),
+            "min_side_width" => self.min_side_width.to_string(),
+            "min_screen_width" => self.min_screen_width.to_string(),
+            "target_side_ratio" => self.target_side_ratio.to_string(),
+            _ => String::new(),
+        }
+    }
+    ///parses a name=value line and applies it with numbers clamped to their range,
+    ///returns false if it could not be parsed
+    fn set_setting(&mut self, s: &str) -> bool {
+        let Some((name, value)) = s.split_once('=') else {
+            return false;
+        };
+        let (name, value) = (name.trim(), value.trim());
+        let (min, max) = match SETTINGS.iter().find(|(n, _)| *n == name) {
+            Some((_, Kind::Float(a, b))) => (*a, *b),
+            Some((_, Kind::Int(a, b))) => (*a as f64, *b as f64),
+            _ => (f64::NEG_INFINITY, f64::INFINITY),
+        };
+        macro_rules! parse {
+            ($v: expr) => {{
+                let Ok(v) = value.parse() else {
+                    return false;
+                };
+                $v = v
+            }};
+        }
+        macro_rules! float {
+            ($v: expr) => {{
+                let Ok(v) = value.parse::<f64>() else {
+                    return false;
+                };
+                if !v.is_finite() {
+                    return false;
+                }
+                $v = v.clamp(min, max) as _
+            }};
+        }
+        macro_rules! int {
+            ($v: expr) => {{
+                let Ok(v) = value.parse::<f64>() else {
+                    return false;
+                };
+                if !v.is_finite() {
+                    return false;
+                }
+                $v = v.clamp(min, max).round() as usize
+            }};
+        }
+        macro_rules! color {
+            ($v: expr) => {{
+                let Some(v) = Color::from_hex(value) else {
+                    return false;
+                };
+                $v = v
+            }};
+        }
+        macro_rules! colors {
+            ($v: expr) => {{
+                let Some(v) = value
+                    .split(',')
+                    .map(|c| Color::from_hex(c.trim()))
+                    .collect::<Option<Vec<Color>>>()
+                else {
+                    return false;
+                };
+                if v.is_empty() {
+                    return false;
+                }
+                $v = v
+            }};
+        }
+        match name {
+            "line_width" => float!(self.line_width),
+            "point_size" => float!(self.point_size),
+            "font_size" => {
+                let v;
+                float!(v);
+                if v != self.font_size {
+                    self.set_font_size(v)
+                }
+            }
+            "side_height" => float!(self.side_height),
+            "line_major" => int!(self.line_major),
+            "line_minor" => int!(self.line_minor),
+            "box_size" => float!(self.box_size),
+            "prec" => float!(self.prec),
+            "mult" => float!(self.mult),
+            "conformal_lines" => int!(self.conformal_lines),
+            "fast_3d" => parse!(self.fast_3d),
+            "fast_3d_move" => parse!(self.fast_3d_move),
+            "reduced_move" => parse!(self.reduced_move),
+            "anti_alias" => parse!(self.anti_alias),
+            "show_box" => parse!(self.show_box),
+            "ignore_bounds" => parse!(self.ignore_bounds),
+            "only_real" => parse!(self.only_real),
+            "log_scale" => parse!(self.log_scale),
+            "domain_alternate" => parse!(self.domain_alternate),
+            "disable_lines" => parse!(self.disable_lines),
+            "disable_axis" => parse!(self.disable_axis),
+            "disable_coord" => parse!(self.disable_coord),
+            "show_contour" => parse!(self.show_contour),
+            "contour_log" => parse!(self.contour_log),
+            "show_zeros" => parse!(self.show_zeros),
+            "show_branch" => parse!(self.show_branch),
+            "show_analysis" => parse!(self.show_analysis),
+            "conformal_polar" => parse!(self.conformal_polar),
+            "lines" => {
+                self.lines = match value {
+                    "lines" => Lines::Lines,
+                    "points" => Lines::Points,
+                    "linespoints" => Lines::LinesPoints,
+                    _ => return false,
+                }
+            }
+            "colormap" => {
+                self.colormap = match value {
+                    "viridis" => Colormap::Viridis,
+                    "inferno" => Colormap::Inferno,
+                    "coolwarm" => Colormap::Coolwarm,
+                    "grayscale" => Colormap::Grayscale,
+                    _ => return false,
+                }
+            }
+            "angle_type" => {
+                self.angle_type = match value {
+                    "radian" => Angle::Radian,
+                    "degree" => Angle::Degree,
+                    "gradian" => Angle::Gradian,
+                    _ => return false,
+                }
+            }
+            "axis_color" => color!(self.axis_color),
+            "axis_color_light" => color!(self.axis_color_light),
+            "background_color" => color!(self.background_color),
+            "text_color" => {
+                let Some(v) = Color::from_hex(value) else {
+                    return false;
+                };
+                if v != self.text_color {
+                    self.set_text_color(v)
+                }
+            }
+            "select_color" => color!(self.select_color),
+            "branch_color" => color!(self.branch_color),
+            "main_colors" => colors!(self.main_colors),
+            "alt_colors" => colors!(self.alt_colors),
+            "min_side_width" => float!(self.min_side_width),
+            "min_screen_width" => float!(self.min_screen_width),
+            "target_side_ratio" => float!(self.target_side_ratio),
+            _ => return false,
+        }
+        true
+    }
+    ///applies every line of the settings menu that parses, leaving the rest untouched
+    pub(crate) fn apply_settings(&mut self) {
+        let Some(lines) = std::mem::take(&mut self.menu_lines) else {
+            return;
+        };
+        for s in &lines {
+            self.set_setting(s);
+        }
+        self.menu_lines = Some(lines);
+        self.contours = None;
+        self.zeros = None;
+        self.branch_cuts = None;
+        self.seams = None;
+        self.analysis = None;
+        self.fit_results = None;
+        self.cache = None;
+        self.recalculate(None);
+    }
+    ///sets the ith setting from a slider position t in [0,1], if it has a range
+    pub(crate) fn slide_setting(&mut self, i: usize, t: f64) {
+        let Some((name, kind)) = SETTINGS.get(i) else {
+            return;
+        };
+        let t = t.clamp(0.0, 1.0);
+        let value = match kind {
+            Kind::Float(a, b) => (((a + t * (b - a)) * 100.0).round() / 100.0).to_string(),
+            Kind::Int(a, b) => ((*a as f64 + t * (b - a) as f64).round() as usize).to_string(),
+            Kind::Bool => (t > 0.5).to_string(),
+            Kind::Other => return,
+        };
+        self.menu_lines.as_mut().unwrap()[i] = format!("{name}={value}");
+        self.apply_settings();
+    }
+    ///slider position in [0,1] of the ith setting, if it has a range
+    pub(crate) fn slider_pos(&self, i: usize) -> Option<f64> {
+        let (name, kind) = SETTINGS.get(i)?;
+        let value = self.get_setting(name);
+        match kind {
+            Kind::Float(a, b) => Some((value.parse::<f64>().ok()? - a) / (b - a)),
+            Kind::Int(a, b) => {
+                Some((value.parse::<usize>().ok()? as f64 - *a as f64) / (*b as f64 - *a as f64))
+            }
+            Kind::Bool => Some(if value.parse::<bool>().ok()? {
+                1.0
+            } else {
+                0.0
+            }),
+            Kind::Other => None,
+        }
+        .map(|t| t.clamp(0.0, 1.0))
+    }
+}
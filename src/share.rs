@@ -0,0 +1,241 @@
+use crate::types::*;
+impl TinyAxis {
+    ///quantizes values to 2^bits levels across their range, keeping non finite values as gaps
+    fn new(values: &[f64], bits: u8) -> Self {
+        let (min, max) = values
+            .iter()
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), v| {
+                (a.min(*v), b.max(*v))
+            });
+        if min > max {
+            return TinyAxis {
+                start: 0.0,
+                step: 0.0,
+                deltas: vec![i64::MIN; values.len()],
+            };
+        }
+        let step = (max - min) / ((1u64 << bits.clamp(1, 52)) - 1) as f64;
+        let mut last = 0;
+        let deltas = values
+            .iter()
+            .map(|v| {
+                if !v.is_finite() {
+                    return i64::MIN;
+                }
+                let n = if step > 0.0 {
+                    ((v - min) / step).round() as i64
+                } else {
+                    0
+                };
+                let d = n - last;
+                last = n;
+                d
+            })
+            .collect();
+        TinyAxis {
+            start: min,
+            step,
+            deltas,
+        }
+    }
+    ///the quantized values, nan for gaps
+    fn values(&self) -> Vec<f64> {
+        let mut n = 0i64;
+        self.deltas
+            .iter()
+            .map(|d| {
+                if *d == i64::MIN {
+                    f64::NAN
+                } else {
+                    n = n.saturating_add(*d);
+                    self.start + self.step * n as f64
+                }
+            })
+            .collect()
+    }
+}
+impl TinySeries {
+    ///a Coord series or Point data evenly thinned to at most max samples, none for other data
+    fn new(index: usize, data: &GraphType, max: usize, bits: u8) -> Option<Self> {
+        let (points, samples) = match data {
+            GraphType::Coord(data) => (false, data.clone()),
+            GraphType::Point(p) => (true, vec![(p.x, Complex::Real(p.y))]),
+            GraphType::List(a) => (
+                true,
+                a.iter()
+                    .map(|data| match data {
+                        GraphType::Point(p) => Some((p.x, Complex::Real(p.y))),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<(f64, Complex)>>>()?,
+            ),
+            _ => return None,
+        };
+        let stride = samples.len().div_ceil(max.max(1)).max(1);
+        let samples = samples
+            .into_iter()
+            .step_by(stride)
+            .collect::<Vec<(f64, Complex)>>();
+        let x = samples.iter().map(|(x, _)| *x).collect::<Vec<f64>>();
+        let (re, im): (Vec<Option<f64>>, Vec<Option<f64>>) =
+            samples.iter().map(|(_, z)| z.to_options()).unzip();
+        let axis = |v: Vec<Option<f64>>| {
+            v.iter().any(|v| v.is_some()).then(|| {
+                let v = v
+                    .into_iter()
+                    .map(|v| v.unwrap_or(f64::NAN))
+                    .collect::<Vec<f64>>();
+                TinyAxis::new(&v, bits)
+            })
+        };
+        Some(TinySeries {
+            index,
+            points,
+            x: TinyAxis::new(&x, bits),
+            re: axis(re),
+            im: axis(im),
+        })
+    }
+    fn len(&self) -> usize {
+        self.x.deltas.len()
+    }
+    ///the series as it goes in Graph.data, Point data as a List of points
+    fn to_graph_type(&self) -> GraphType {
+        let x = self.x.values();
+        let re = self.re.as_ref().map(TinyAxis::values);
+        let im = self.im.as_ref().map(TinyAxis::values);
+        let part = |v: &Option<Vec<f64>>, i: usize| {
+            v.as_ref().map(|v| v.get(i).copied().unwrap_or(f64::NAN))
+        };
+        let z = |i: usize| match (part(&re, i), part(&im, i)) {
+            (Some(a), Some(b)) => Complex::Complex(a, b),
+            (Some(a), None) => Complex::Real(a),
+            (None, Some(b)) => Complex::Imag(b),
+            (None, None) => Complex::Real(f64::NAN),
+        };
+        if self.points {
+            let mut pts = x
+                .iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    GraphType::Point(Vec2::new(*x, z(i).to_options().0.unwrap_or(f64::NAN)))
+                })
+                .collect::<Vec<GraphType>>();
+            if pts.len() == 1 {
+                pts.remove(0)
+            } else {
+                GraphType::List(pts)
+            }
+        } else {
+            GraphType::Coord(x.iter().enumerate().map(|(i, x)| (*x, z(i))).collect())
+        }
+    }
+}
+///largest number of samples accepted across the series of a decoded GraphTiny
+pub(crate) const MAX_SHARE_SAMPLES: usize = 1 << 22;
+impl GraphTiny {
+    ///rejects shared data with more than MAX_SHARE_SAMPLES samples or mismatched axes
+    pub(crate) fn check_data(&self) -> Result<(), RuplError> {
+        let mut total = 0usize;
+        for series in &self.data {
+            let n = series.len();
+            if [&series.re, &series.im]
+                .into_iter()
+                .flatten()
+                .any(|a| a.deltas.len() != n)
+            {
+                return Err(RuplError::Decode(format!(
+                    "series {} has axes of different lengths",
+                    series.index
+                )));
+            }
+            total = total.saturating_add(n);
+        }
+        if total > MAX_SHARE_SAMPLES {
+            return Err(RuplError::Decode(format!(
+                "{total} shared samples is more than {MAX_SHARE_SAMPLES}"
+            )));
+        }
+        Ok(())
+    }
+}
+impl Graph {
+    ///each Coord and Point series thinned to at most max samples
+    fn share_series(&self, max: usize, bits: u8) -> Vec<TinySeries> {
+        self.data
+            .iter()
+            .enumerate()
+            .filter_map(|(k, data)| TinySeries::new(k, data, max, bits))
+            .collect()
+    }
+    ///a compressed GraphTiny like the one copied by the save keybind that also carries
+    ///the Coord and Point data, quantized to the given bits per value and delta encoded,
+    ///so it can be pasted where the names can not be evaluated,
+    ///
+    ///the longest series are evenly thinned until the string is at most limit bytes
+    ///and carries at most MAX_SHARE_SAMPLES samples,
+    ///returns the string with the number of samples kept and the total number of samples
+    pub fn to_share(&self, bits: u8, limit: usize) -> (String, usize, usize) {
+        let count = |data: &[TinySeries]| data.iter().map(TinySeries::len).sum::<usize>();
+        let mut tiny = self.to_tiny();
+        tiny.data = self.share_series(usize::MAX, bits);
+        let total = count(&tiny.data);
+        let mut max = tiny.data.iter().map(TinySeries::len).max().unwrap_or(0);
+        let mut share = String::from(&tiny);
+        let mut kept = total;
+        while (share.len() > limit || kept > MAX_SHARE_SAMPLES) && kept > 0 {
+            let ratio =
+                (share.len() as f64 / limit as f64).max(kept as f64 / MAX_SHARE_SAMPLES as f64);
+            max = ((max as f64 / ratio).floor() as usize).min(max - 1);
+            tiny.data = if max == 0 {
+                Vec::new()
+            } else {
+                self.share_series(max, bits)
+            };
+            share = String::from(&tiny);
+            kept = count(&tiny.data);
+        }
+        (share, kept, total)
+    }
+    ///fills the series carried by a GraphTiny, the others are evaluated from their names
+    pub(crate) fn apply_tiny_data(&mut self, data: Vec<TinySeries>) {
+        self.shared = data
+            .iter()
+            .filter_map(|series| {
+                let name = self.names.get(series.index)?.name.clone();
+                Some((series.index, name, series.to_graph_type()))
+            })
+            .collect();
+        if self.shared.is_empty() {
+            return;
+        }
+        self.keep_shared();
+        self.cache = None;
+        self.analysis = None;
+        self.fit_results = None;
+        self.seams = None;
+        self.reset_3d_if_changed();
+    }
+    ///puts the shared series back over evaluated data, dropping those whose name was edited
+    pub(crate) fn keep_shared(&mut self) {
+        let names = &self.names;
+        self.shared
+            .retain(|(k, name, _)| names.get(*k).is_some_and(|n| n.name == *name));
+        for (k, _, data) in &self.shared {
+            if self.data.len() <= *k {
+                self.data.resize(k + 1, GraphType::List(Vec::new()))
+            }
+            self.data[*k] = data.clone();
+        }
+    }
+    ///weather every named function has shared data, so there is nothing to evaluate
+    pub(crate) fn all_shared(&self) -> bool {
+        !self.shared.is_empty()
+            && self
+                .names
+                .iter()
+                .enumerate()
+                .all(|(k, n)| n.name.is_empty() || self.shared.iter().any(|(j, _, _)| *j == k))
+    }
+}
@@ -29,16 +29,13 @@ impl Graph {
         let ti = (t / delta).round().max(1.0);
         self.text_scroll_pos.1 = (ti as usize + self.text_scroll_pos.0) - 1;
         let delta = t / ti;
+        let width = if is_portrait {
+            self.screen.x as f32
+        } else {
+            offset.x
+        };
         for i in 0..ti as usize {
-            painter.hline(
-                if is_portrait {
-                    self.screen.x as f32
-                } else {
-                    offset.x
-                },
-                i as f32 * delta,
-                &self.axis_color,
-            )
+            painter.hline(width, i as f32 * delta, &self.axis_color)
         }
         if let (Some((a, b, _)), Some((_, y))) = (self.select, self.text_box) {
             painter.highlight(
@@ -49,7 +46,7 @@ impl Graph {
                 &self.select_color,
             )
         }
-        self.display_names(painter, delta);
+        self.display_names(painter, delta, width);
         if let Some(text_box) = self.text_box {
             let x = text_box.0 as f32 * self.font_width;
             let y = (text_box.1 as isize - self.text_scroll_pos.0 as isize) as f32 * delta;
@@ -147,7 +144,16 @@ impl Graph {
             }
             if i.pointer_right.is_some() {
                 if let Some(last) = self.last_right_interact {
-                    if let Some(new) = self.side_slider {
+                    if let Some(new) = self.side_slider
+                        && matches!(self.menu, Menu::Settings)
+                    {
+                        let w = if is_portrait {
+                            self.screen.x
+                        } else {
+                            self.draw_offset.x as f64
+                        } - 8.0;
+                        self.slide_setting(new, x / w);
+                    } else if let Some(new) = self.side_slider {
                         let delta = ((mpos.x - last.x) / 64.0).exp();
                         let name = self.get_name(new).to_string();
                         let mut body = |s: String| {
@@ -162,6 +168,18 @@ impl Graph {
                             body(format!("{}={}", a, f * delta))
                         }
                     }
+                } else if i.pointer_right == Some(true)
+                    && !main_graph
+                    && matches!(self.menu, Menu::Settings)
+                {
+                    let new = new as usize + self.text_scroll_pos.0;
+                    self.side_slider = Some(new);
+                    let w = if is_portrait {
+                        self.screen.x
+                    } else {
+                        self.draw_offset.x as f64
+                    } - 8.0;
+                    self.slide_setting(new, x / w);
                 } else if i.pointer_right.unwrap() && mpos.x < 0.0 {
                     self.side_slider = Some(new as usize);
                 } else {
@@ -172,7 +190,10 @@ impl Graph {
                 self.side_slider = None;
                 self.last_right_interact = None
             }
-            if x < 0.0 && i.pointer.unwrap_or(false) {
+            if x < 0.0
+                && i.pointer.unwrap_or(false)
+                && matches!(self.menu, Menu::Side | Menu::Normal)
+            {
                 if let Some(n) = self
                     .blacklist_graphs
                     .iter()
@@ -201,7 +222,12 @@ impl Graph {
         let Some(mut text_box) = self.text_box else {
             unreachable!()
         };
-        for key in &i.keys_pressed {
+        let keys: &[Key] = if matches!(self.menu, Menu::Keybinds) {
+            &[]
+        } else {
+            &i.keys_pressed
+        };
+        for key in keys {
             let down = |g: &Graph, text_box: &mut (usize, usize)| {
                 text_box.1 += 1;
                 if !matches!(g.menu, Menu::Normal | Menu::Side) && text_box.1 == g.get_name_len() {
@@ -273,11 +299,11 @@ impl Graph {
                         let (a, b, _) = self.select.unwrap_or_default();
                         if a != b {
                             let text = &self.get_name(text_box.1)[a..b].to_string();
-                            self.clipboard.as_mut().unwrap().set_text(text)
+                            self.copy_text(text)
                         }
                     }
                     'v' => {
-                        let s = self.clipboard.as_mut().unwrap().get_text();
+                        let s = self.paste_text();
                         if !s.is_empty() {
                             let (a, b, _) = self.select.unwrap_or_default();
                             if a != b {
@@ -298,7 +324,7 @@ impl Graph {
                         if a != b {
                             self.select = None;
                             let text = self.remove_str(text_box.1, a, b);
-                            self.clipboard.as_mut().unwrap().set_text(&text);
+                            self.copy_text(&text);
                             text_box.0 = a;
                             self.history_push(Change::Str(text_box, text, true));
                             self.name_modified(Some(text_box.1));
@@ -599,8 +625,10 @@ impl Graph {
         self.text_box = Some(text_box);
         text_box.1 = self.expand_names(text_box.1);
         #[cfg(feature = "serde")]
-        if matches!(self.menu, Menu::Load) {
-            self.load(text_box.1)
+        if matches!(self.menu, Menu::Load)
+            && let Some(j) = self.load_row(text_box.1)
+        {
+            self.load(j)
         }
         true
     }
@@ -759,11 +787,12 @@ impl Graph {
                 i + 1
             }
             #[cfg(feature = "serde")]
-            Menu::Load => self.file_data.as_ref().unwrap().len() + 1,
-            Menu::Settings => todo!(),
+            Menu::Load => self.load_rows().len() + 2,
+            Menu::Settings | Menu::Keybinds => self.menu_lines.as_ref().unwrap().len() + 1,
         }
     }
-    pub(crate) fn display_names(&self, painter: &mut Painter, delta: f32) {
+    #[cfg_attr(not(feature = "serde"), allow(unused_variables))]
+    pub(crate) fn display_names(&self, painter: &mut Painter, delta: f32, width: f32) {
         match self.menu {
             Menu::Side | Menu::Normal => {
                 let mut text = |s: &str, i: usize, color: (Option<Color>, Option<Color>)| {
@@ -852,19 +881,133 @@ impl Graph {
                     }
                     i += 1;
                 }
+                if self.graph_mode == GraphMode::DomainColoring && self.show_zeros {
+                    for (l, s) in self.zeros_list().iter().enumerate() {
+                        if i + l + 1 >= self.text_scroll_pos.0 {
+                            text(s, i + l + 1 - self.text_scroll_pos.0, (None, None));
+                        }
+                    }
+                }
+                if self.show_analysis && !self.is_3d && self.graph_mode == GraphMode::Normal {
+                    for (l, s) in self.analysis_list().iter().enumerate() {
+                        if i + l + 1 >= self.text_scroll_pos.0 {
+                            text(s, i + l + 1 - self.text_scroll_pos.0, (None, None));
+                        }
+                    }
+                    i += self.analysis_list().len();
+                }
+                if !self.is_3d {
+                    for (l, s) in self.fit_list().iter().enumerate() {
+                        if i + l + 1 >= self.text_scroll_pos.0 {
+                            text(s, i + l + 1 - self.text_scroll_pos.0, (None, None));
+                        }
+                    }
+                }
             }
             #[cfg(feature = "serde")]
             Menu::Load => {
-                for (i, n) in self.file_data.as_ref().unwrap().iter().enumerate() {
+                let fd = self.file_data.as_ref().unwrap();
+                let rows = self.load_rows();
+                let y = |i: usize| (i as f32 - self.text_scroll_pos.0 as f32) * delta;
+                if self.load_filter.is_empty() {
+                    self.text(
+                        Pos::new(4.0, y(0) + delta / 2.0),
+                        Align::LeftCenter,
+                        "search",
+                        &self.axis_color_light,
+                        painter,
+                    );
+                } else {
                     self.text_color(
-                        Pos::new(4.0, i as f32 * delta + delta / 2.0),
+                        Pos::new(4.0, y(0) + delta / 2.0),
                         Align::LeftCenter,
-                        &n.0,
+                        &self.load_filter,
+                        painter,
+                    )
+                }
+                for (i, e) in rows.iter().map(|j| &fd[*j]).enumerate() {
+                    let y = y(i + 1);
+                    self.text_color(
+                        Pos::new(4.0, y + delta / 2.0),
+                        Align::LeftCenter,
+                        &e.name,
+                        painter,
+                    );
+                    let s = delta - 2.0;
+                    self.draw_thumbnail(painter, Pos::new(width - s - 2.0, y + 1.0), s, e);
+                }
+                let selected = self
+                    .text_box
+                    .and_then(|(_, i)| self.load_row(i))
+                    .or(self.save_num);
+                if let Some(e) = selected.and_then(|j| fd.get(j))
+                    && e.version >= 2
+                {
+                    let mut y = y(rows.len() + 2);
+                    let s = (width - 8.0).min(4.0 * THUMBNAIL as f32);
+                    if !e.meta.thumbnail.is_empty() {
+                        self.draw_thumbnail(painter, Pos::new(4.0, y), s, e);
+                        y += s;
+                    }
+                    let mut lines = vec![format!("{:?}", e.meta.graph_mode)];
+                    if e.meta.time != 0 {
+                        lines.push(crate::format_time(e.meta.time));
+                    }
+                    for l in lines {
+                        self.text(
+                            Pos::new(4.0, y + delta / 2.0),
+                            Align::LeftCenter,
+                            &l,
+                            &self.axis_color_light,
+                            painter,
+                        );
+                        y += delta;
+                    }
+                    for n in &e.meta.names {
+                        self.text_color(
+                            Pos::new(4.0, y + delta / 2.0),
+                            Align::LeftCenter,
+                            n,
+                            painter,
+                        );
+                        y += delta;
+                    }
+                }
+            }
+            Menu::Settings | Menu::Keybinds => {
+                let w = self.side_bar_width as f32 - 8.0;
+                for (i, n) in self
+                    .menu_lines
+                    .as_ref()
+                    .unwrap()
+                    .iter()
+                    .enumerate()
+                    .skip(self.text_scroll_pos.0)
+                {
+                    let y = (i - self.text_scroll_pos.0) as f32 * delta;
+                    if matches!(self.menu, Menu::Settings)
+                        && let Some(t) = self.slider_pos(i)
+                    {
+                        let y = y + delta - 3.0;
+                        painter.line_segment(
+                            [Pos::new(4.0, y), Pos::new(4.0 + w, y)],
+                            1.0,
+                            &self.axis_color_light,
+                        );
+                        painter.line_segment(
+                            [Pos::new(4.0, y), Pos::new(4.0 + w * t as f32, y)],
+                            3.0,
+                            &self.select_color,
+                        );
+                    }
+                    self.text_color(
+                        Pos::new(4.0, y + delta / 2.0),
+                        Align::LeftCenter,
+                        n,
                         painter,
                     )
                 }
             }
-            Menu::Settings => todo!(),
         }
     }
     pub(crate) fn get_name(&self, mut i: usize) -> &str {
@@ -883,13 +1026,14 @@ impl Graph {
                 ""
             }
             #[cfg(feature = "serde")]
-            Menu::Load => self
-                .file_data
-                .as_ref()
-                .unwrap()
-                .get(i)
-                .map_or("", |(a, _, _)| a),
-            Menu::Settings => todo!(),
+            Menu::Load => match self.load_row(i) {
+                Some(j) => &self.file_data.as_ref().unwrap()[j].name,
+                None if i == 0 => &self.load_filter,
+                None => "",
+            },
+            Menu::Settings | Menu::Keybinds => {
+                self.menu_lines.as_ref().unwrap().get(i).map_or("", |a| a)
+            }
         }
     }
     pub(crate) fn get_name_count(&self, mut i: usize) -> usize {
@@ -908,13 +1052,13 @@ impl Graph {
                 0
             }
             #[cfg(feature = "serde")]
-            Menu::Load => self
-                .file_data
+            Menu::Load => self.get_name(i).chars().count(),
+            Menu::Settings | Menu::Keybinds => self
+                .menu_lines
                 .as_ref()
                 .unwrap()
                 .get(i)
-                .map_or(0, |(a, _, _)| a.chars().count()),
-            Menu::Settings => todo!(),
+                .map_or(0, |a| a.chars().count()),
         }
     }
     pub(crate) fn get_mut_name(&mut self, mut i: usize) -> &mut String {
@@ -933,8 +1077,18 @@ impl Graph {
                 unreachable!()
             }
             #[cfg(feature = "serde")]
-            Menu::Load => &mut self.file_data.as_mut().unwrap()[i].0,
-            Menu::Settings => todo!(),
+            Menu::Load => {
+                if i == 0 {
+                    self.load_frozen = None;
+                    return &mut self.load_filter;
+                }
+                if self.load_frozen.is_none() {
+                    self.load_frozen = Some(self.load_rows())
+                }
+                let j = self.load_row(i).unwrap();
+                &mut self.file_data.as_mut().unwrap()[j].name
+            }
+            Menu::Settings | Menu::Keybinds => &mut self.menu_lines.as_mut().unwrap()[i],
         }
     }
     pub(crate) fn get_longest(&self) -> usize {
@@ -956,10 +1110,18 @@ impl Graph {
                 .as_ref()
                 .unwrap()
                 .iter()
-                .map(|a| a.0.len())
+                .map(|e| e.name.len())
+                .chain(std::iter::once(self.load_filter.len()))
+                .max()
+                .unwrap_or_default(),
+            Menu::Settings | Menu::Keybinds => self
+                .menu_lines
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|a| a.len())
                 .max()
                 .unwrap_or_default(),
-            Menu::Settings => todo!(),
         }
     }
     pub(crate) fn modify_name(&mut self, i: usize, j: usize, char: String) -> bool {
@@ -998,11 +1160,17 @@ impl Graph {
             }
             #[cfg(feature = "serde")]
             Menu::Load => {
+                let i = self.load_row(i)?;
+                self.load_frozen = None;
                 let d = self.file_data.as_mut().unwrap();
                 d.remove(i);
-                if self.save_num == Some(i) {
-                    self.data.clear();
-                    self.save_num = None
+                match self.save_num {
+                    Some(n) if n == i => {
+                        self.data.clear();
+                        self.save_num = None
+                    }
+                    Some(n) if n > i => self.save_num = Some(n - 1),
+                    _ => {}
                 }
                 if d.is_empty() {
                     self.save_num = None;
@@ -1010,7 +1178,7 @@ impl Graph {
                 }
                 return Some(false);
             }
-            Menu::Settings => todo!(),
+            Menu::Settings | Menu::Keybinds => {}
         }
         None
     }
@@ -1052,10 +1220,19 @@ impl Graph {
             }
             #[cfg(feature = "serde")]
             Menu::Load => {
+                let Some(i) = j.checked_sub(1).and_then(|j| self.load_row(j)) else {
+                    return;
+                };
+                self.load_frozen = None;
                 let fd = self.file_data.as_mut().unwrap();
-                fd.insert(j, fd[j - 1].clone())
+                fd.insert(i + 1, fd[i].clone());
+                if let Some(n) = self.save_num.as_mut()
+                    && *n > i
+                {
+                    *n += 1
+                }
             }
-            Menu::Settings => todo!(),
+            Menu::Settings | Menu::Keybinds => {}
         }
     }
     pub fn index_to_name(
@@ -1083,7 +1260,7 @@ impl Graph {
             }
             #[cfg(feature = "serde")]
             Menu::Load => (None, None),
-            Menu::Settings => (None, None),
+            Menu::Settings | Menu::Keybinds => (None, None),
         }
     }
     pub(crate) fn remove_char(&mut self, i: usize, j: usize) -> char {
@@ -1113,8 +1290,52 @@ impl Graph {
                 i
             }
             #[cfg(feature = "serde")]
-            Menu::Load => self.file_data.as_ref().unwrap().len(),
-            Menu::Settings => todo!(),
+            Menu::Load => self.load_rows().len() + 1,
+            Menu::Settings | Menu::Keybinds => self.menu_lines.as_ref().unwrap().len(),
+        }
+    }
+    #[cfg(feature = "serde")]
+    ///indices of the saves whose name, graph mode or function names contain Graph.load_filter,
+    ///kept as they were while a save is being renamed
+    pub(crate) fn load_rows(&self) -> Vec<usize> {
+        if let Some(rows) = &self.load_frozen {
+            return rows.clone();
+        }
+        let filter = self.load_filter.to_lowercase();
+        let matches = |s: &str| s.to_lowercase().contains(&filter);
+        self.file_data
+            .as_ref()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                filter.is_empty()
+                    || matches(&e.name)
+                    || matches(&format!("{:?}", e.meta.graph_mode))
+                    || e.meta.names.iter().any(|n| matches(n))
+            })
+            .map(|(j, _)| j)
+            .collect()
+    }
+    #[cfg(feature = "serde")]
+    ///the save shown on the ith line of the load menu, after the search line
+    pub(crate) fn load_row(&self, i: usize) -> Option<usize> {
+        self.load_rows().get(i.checked_sub(1)?).copied()
+    }
+    #[cfg(feature = "serde")]
+    ///draws the thumbnail of a save as a square of side s with its top left at pos
+    fn draw_thumbnail(&self, painter: &mut Painter, pos: Pos, s: f32, e: &SaveEntry) {
+        if e.meta.thumbnail.len() != THUMBNAIL * THUMBNAIL * 3 {
+            return;
+        }
+        let p = s / THUMBNAIL as f32;
+        for (k, c) in e.meta.thumbnail.chunks_exact(3).enumerate() {
+            let (i, j) = ((k % THUMBNAIL) as f32, (k / THUMBNAIL) as f32);
+            painter.rect_filled(
+                Pos::new(pos.x + (i + 0.5) * p, pos.y + (j + 0.5) * p),
+                &Color::new(c[0], c[1], c[2]),
+                p.ceil(),
+            )
         }
     }
     pub(crate) fn history_push(&mut self, c: Change) {
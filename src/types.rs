@@ -28,6 +28,14 @@ pub enum GraphMode {
     Polar,
     ///takes a slice of a 3d function and applys polar logic
     SlicePolar,
+    ///graphs the real part of the 3d data set as an image, colored by Graph.colormap
+    Heatmap,
+    ///maps the domain coloring of the 3d data set onto the riemann sphere via stereographic projection,
+    ///see Prec::Sphere for the expected data
+    RiemannSphere,
+    ///draws a grid over the domain of the 3d data set on the left and its image under the function on the right,
+    ///the domain is given by Graph.var, see Graph.conformal_polar
+    Conformal,
 }
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
@@ -54,8 +62,65 @@ pub enum GraphType {
     Point(Vec2),
     ///a list of graphs, so that all graphs will be the same color
     List(Vec<GraphType>),
+    ///riemann surface of a multivalued function, each sheet is laid out like Width3D over the same bounds,
+    ///
+    ///the last element lists pairs of sheets that continue into each other across a branch cut,
+    ///if empty any sheet may continue into any other,
+    ///the continuation is taken as the connected sheet with the closest value
+    ///
+    ///displayed in 3d with height as the real part and color as the imaginary part, see Graph.show
+    Surface(Vec<Vec<Complex>>, f64, f64, f64, f64, Vec<(usize, usize)>),
     None,
 }
+///errors from saving, loading, sharing, fonts and the clipboard
+#[derive(Debug)]
+pub enum RuplError {
+    ///a file could not be read or written
+    Io(std::io::Error),
+    ///data could not be serialized or parsed
+    Decode(String),
+    ///data could not be compressed or decompressed
+    Decompress(String),
+    ///font data could not be read
+    Font(String),
+    ///the system clipboard could not be accessed
+    Clipboard(String),
+    ///data was saved by a newer version
+    Version(u32),
+    ///only the first number of points out of the second fit in a share string
+    Truncated(usize, usize),
+}
+impl std::fmt::Display for RuplError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuplError::Io(e) => write!(f, "io error: {e}"),
+            RuplError::Decode(e) => write!(f, "decode error: {e}"),
+            RuplError::Decompress(e) => write!(f, "decompress error: {e}"),
+            RuplError::Font(e) => write!(f, "font error: {e}"),
+            RuplError::Clipboard(e) => write!(f, "clipboard error: {e}"),
+            RuplError::Version(v) => write!(f, "unsupported save version {v}"),
+            RuplError::Truncated(a, b) => {
+                write!(
+                    f,
+                    "shared {a} of {b} points, the rest did not fit the share limit"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for RuplError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RuplError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+impl From<std::io::Error> for RuplError {
+    fn from(value: std::io::Error) -> Self {
+        RuplError::Io(value)
+    }
+}
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct Name {
@@ -88,6 +153,11 @@ pub enum Prec {
     Slice(f64),
     ///the amount of x/y data is requested for domain coloring
     Dimension(usize, usize),
+    ///a multiplier on the precision of the graph to update data on, potentially note Graph.prec
+    ///
+    ///expecting a GraphType::List of 2 Width3D data sets over the requested bounds,
+    ///the first of f(z) and the second of f(1/z), together covering the riemann sphere
+    Sphere(f64),
 }
 pub enum Bound {
     ///a 2d data set is requested
@@ -121,6 +191,103 @@ pub enum Lines {
     Lines,
 }
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum Companion {
+    ///finite difference derivative, central in the interior and one sided at the ends
+    Derivative,
+    ///cumulative trapezoid integral starting from zero at the first sample
+    Integral,
+}
+///model fitted by least squares to the Coord or Point data of a series
+#[derive(Clone)]
+pub enum FitModel {
+    ///y=a+bx
+    Linear,
+    ///y=a+bx+cx^2+... of the given degree
+    Polynomial(usize),
+    ///y=a*exp(bx), fitted on the logarithm of the points with positive y
+    Exponential,
+    ///y=a*x^b, fitted on the logarithm of the points with positive x and y
+    Power,
+    ///y=f(p,x) with parameters p starting from the given guess,
+    ///fitted by levenberg marquardt
+    #[allow(clippy::type_complexity)]
+    Custom(std::rc::Rc<dyn Fn(&[f64], f64) -> f64>, Vec<f64>),
+}
+///how columns of a csv file map to a series
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CsvColumn {
+    ///a column of real values
+    Real(usize),
+    ///a column of real parts and a column of imaginary parts
+    Complex(usize, usize),
+}
+///options for GraphType::from_csv and Graph::load_csv
+#[derive(Clone, Debug, Default)]
+pub struct CsvOptions {
+    ///field separator, detected from the first line as a tab, semicolon or comma if none
+    pub delimiter: Option<char>,
+    ///if the first line names the columns, detected as a line with a non numeric field if none
+    pub header: Option<bool>,
+    ///column of the x values, the row number if none
+    pub x: Option<usize>,
+    ///column of the y values, making Coord3D series if some
+    pub y: Option<usize>,
+    ///columns to make series from, every column besides x and y as real values if empty
+    pub columns: Vec<CsvColumn>,
+}
+#[cfg(feature = "serde")]
+///side length of the thumbnail stored with each save
+pub(crate) const THUMBNAIL: usize = 24;
+#[cfg(feature = "serde")]
+///details stored with a save to describe it in the load menu
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct SaveMeta {
+    ///seconds since the unix epoch when it was saved, 0 if unknown
+    pub(crate) time: u64,
+    pub(crate) graph_mode: GraphMode,
+    ///every non empty function name
+    pub(crate) names: Vec<String>,
+    ///THUMBNAIL by THUMBNAIL rgb pixels of the plot, read back from the frame with tiny-skia
+    ///and raster skia, other backends like skia-vulkan, egui and wasm only store one if the
+    ///host passes its frame to Graph::set_thumbnail, otherwise it is empty
+    pub(crate) thumbnail: Vec<u8>,
+}
+#[cfg(feature = "serde")]
+///an entry of the save file
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SaveEntry {
+    pub(crate) name: String,
+    ///uncompressed length of the bitcode data, 0 for json
+    pub(crate) len: usize,
+    pub(crate) data: String,
+    pub(crate) version: u32,
+    ///empty for entries older than version 2
+    pub(crate) meta: SaveMeta,
+    ///the entry as read if it could not be parsed, written back unchanged
+    pub(crate) raw: Option<String>,
+}
+///text format for Graph::export_data
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ExportFormat {
+    ///one row per sample with columns series, name, x, y for 3d data, re and im
+    #[default]
+    Csv,
+    #[cfg(feature = "serde")]
+    ///an array of series with their name and arrays of x, y for 3d data, re and im
+    Json,
+}
+///result of a fit
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fit {
+    ///coefficients of the model, lowest degree first for polynomials,
+    ///(a, b) for exponential and power laws, the parameters for custom models
+    pub coefficients: Vec<f64>,
+    ///coefficient of determination against the original data
+    pub r2: f64,
+}
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Copy, Default)]
 pub enum DepthColor {
     ///colors based off of how far on the z axis the value is
@@ -172,6 +339,48 @@ impl Angle {
     }
 }
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Copy, PartialEq, Default)]
+pub enum Colormap {
+    #[default]
+    Viridis,
+    Inferno,
+    Coolwarm,
+    Grayscale,
+}
+impl Colormap {
+    ///maps t in [0,1] to an rgb color in [0,1]
+    pub(crate) fn to_rgb(self, t: f64) -> (f64, f64, f64) {
+        let stops: &[[u8; 3]] = match self {
+            Colormap::Viridis => &[
+                [68, 1, 84],
+                [59, 82, 139],
+                [33, 145, 140],
+                [94, 201, 98],
+                [253, 231, 37],
+            ],
+            Colormap::Inferno => &[
+                [0, 0, 4],
+                [87, 16, 110],
+                [188, 55, 84],
+                [249, 142, 9],
+                [252, 255, 164],
+            ],
+            Colormap::Coolwarm => &[[59, 76, 192], [221, 221, 221], [180, 4, 38]],
+            Colormap::Grayscale => &[[0, 0, 0], [255, 255, 255]],
+        };
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) } * (stops.len() - 1) as f64;
+        let i = (t.floor() as usize).min(stops.len() - 2);
+        let f = t - i as f64;
+        let (a, b) = (stops[i], stops[i + 1]);
+        let lerp = |n: usize| (a[n] as f64 * (1.0 - f) + b[n] as f64 * f) / 255.0;
+        (lerp(0), lerp(1), lerp(2))
+    }
+    pub(crate) fn to_color(self, t: f64) -> Color {
+        let (r, g, b) = self.to_rgb(t);
+        Color::new((255.0 * r) as u8, (255.0 * g) as u8, (255.0 * b) as u8)
+    }
+}
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub(crate) enum Change {
     Char((usize, usize), char, bool),
@@ -179,6 +388,68 @@ pub(crate) enum Change {
     Line(usize, bool, bool),
     None,
 }
+///the parts of the graph which describe where it is being looked at from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct View {
+    pub(crate) offset: Vec2,
+    pub(crate) zoom: Vec2,
+    pub(crate) offset3d: Vec3,
+    pub(crate) zoom_3d: Vec3,
+    pub(crate) angle: Vec2,
+    pub(crate) graph_mode: GraphMode,
+    pub(crate) slice: isize,
+    pub(crate) var: Vec2,
+}
+///a point found by the analysis layer, with the series it belongs to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Feature {
+    Zero(usize),
+    Min(usize),
+    Max(usize),
+    Intersection(usize, usize),
+}
+///screen positions of scattered data points along with their series, index and value,
+///bucketed into a grid of cells for nearest point lookups
+#[derive(Default)]
+pub(crate) struct PointIndex {
+    cell: f32,
+    cells: std::collections::HashMap<(i32, i32), Vec<usize>>,
+    points: Vec<(Pos, usize, usize, f64, Complex)>,
+}
+impl PointIndex {
+    pub(crate) fn new(cell: f32) -> Self {
+        Self {
+            cell,
+            ..Default::default()
+        }
+    }
+    fn key(&self, p: Pos) -> (i32, i32) {
+        (
+            (p.x / self.cell).floor() as i32,
+            (p.y / self.cell).floor() as i32,
+        )
+    }
+    pub(crate) fn insert(&mut self, p: Pos, series: usize, index: usize, x: f64, z: Complex) {
+        if !p.x.is_finite() || !p.y.is_finite() {
+            return;
+        }
+        let key = self.key(p);
+        self.cells.entry(key).or_default().push(self.points.len());
+        self.points.push((p, series, index, x, z))
+    }
+    ///nearest point no further than the cell size away
+    pub(crate) fn nearest(&self, p: Pos) -> Option<(Pos, usize, usize, f64, Complex)> {
+        let (kx, ky) = self.key(p);
+        let d = |q: &Pos| (q.x - p.x) * (q.x - p.x) + (q.y - p.y) * (q.y - p.y);
+        (kx - 1..=kx + 1)
+            .flat_map(|x| (ky - 1..=ky + 1).map(move |y| (x, y)))
+            .filter_map(|k| self.cells.get(&k))
+            .flatten()
+            .map(|i| self.points[*i])
+            .filter(|(q, ..)| d(q) <= self.cell * self.cell)
+            .min_by(|a, b| d(&a.0).total_cmp(&d(&b.0)))
+    }
+}
 #[cfg(feature = "arboard")]
 pub(crate) struct Clipboard(pub arboard::Clipboard);
 #[cfg(feature = "arboard")]
@@ -192,30 +463,40 @@ impl std::fmt::Debug for Clipboard {
 pub(crate) struct Clipboard(pub(crate) String);
 impl Clipboard {
     #[cfg(feature = "arboard")]
-    pub(crate) fn set_text(&mut self, text: &str) {
-        self.0.set_text(text).unwrap_or_default()
+    pub(crate) fn set_text(&mut self, text: &str) -> Result<(), RuplError> {
+        self.0
+            .set_text(text)
+            .map_err(|e| RuplError::Clipboard(e.to_string()))
     }
     #[cfg(feature = "arboard")]
-    pub(crate) fn set_image(&mut self, width: usize, height: usize, bytes: &[u8]) {
+    pub(crate) fn set_image(
+        &mut self,
+        width: usize,
+        height: usize,
+        bytes: &[u8],
+    ) -> Result<(), RuplError> {
         self.0
             .set_image(arboard::ImageData {
                 width,
                 height,
                 bytes: bytes.into(),
             })
-            .unwrap()
+            .map_err(|e| RuplError::Clipboard(e.to_string()))
     }
     #[cfg(not(feature = "arboard"))]
-    pub(crate) fn set_text(&mut self, text: &str) {
+    pub(crate) fn set_text(&mut self, text: &str) -> Result<(), RuplError> {
         self.0 = text.to_string();
+        Ok(())
     }
     #[cfg(feature = "arboard")]
-    pub(crate) fn get_text(&mut self) -> String {
-        self.0.get_text().unwrap_or_default()
+    pub(crate) fn get_text(&mut self) -> Result<String, RuplError> {
+        self.0
+            .get_text()
+            .map_err(|e| RuplError::Clipboard(e.to_string()))
     }
     #[cfg(not(feature = "arboard"))]
-    pub(crate) fn get_text(&mut self) -> String {
-        self.0.clone()
+    pub(crate) fn get_text(&mut self) -> Result<String, RuplError> {
+        Ok(self.0.clone())
     }
 }
 #[cfg(feature = "tiny-skia")]
@@ -316,6 +597,105 @@ pub struct Graph {
     ///alternate domain coloring mode
     #[cfg_attr(feature = "serde", serde(default))]
     pub domain_alternate: bool,
+    ///colormap used by heatmap mode
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colormap: Colormap,
+    ///fixed min/max of heatmap mode, if None then it is taken from the data
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub heatmap_range: Option<Vec2>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) heatmap_bound: Vec2,
+    ///width and height of the last Width3D data requested for domain coloring or heatmaps
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) image_dims: (usize, usize),
+    ///draws contour lines of modulus and argument in domain coloring
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_contour: bool,
+    ///spacing between modulus contour lines, in log10 if Graph.contour_log, None to disable
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub contour_abs: Option<f64>,
+    ///weather modulus contour lines are spaced logarithmically
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub contour_log: bool,
+    ///argument contour lines are drawn every pi/k, None to disable
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub contour_arg: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) contours: Option<Vec<(Vec2, Vec2)>>,
+    ///marks zeros and poles in domain coloring, found by the winding number of the argument
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_zeros: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) zeros: Option<Vec<(Vec2, isize)>>,
+    ///draws an overlay listing the keybinds relevant to the current mode
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_help: bool,
+    ///highlights branch cuts in domain coloring and draws them as seams in 3d,
+    ///found where the argument jumps well past its change between neighbouring points without a zero or pole
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_branch: bool,
+    ///color of branch cut lines
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub branch_color: Color,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) branch_cuts: Option<Vec<(Vec2, Vec2)>>,
+    #[allow(clippy::type_complexity)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) seams: Option<Vec<Vec<(Vec<bool>, Vec<bool>)>>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) point_index: PointIndex,
+    #[allow(clippy::type_complexity)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ///called with the series and index of a Coord or Point data point when it is clicked,
+    ///indices count through List data in order
+    pub point_click: Option<Box<dyn FnMut(usize, usize)>>,
+    ///extra curves computed from the Width or Coord data of the series at the given index,
+    ///drawn after the data and labeled like "d/dx f" or "∫f dx"
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub companions: Vec<(usize, Companion)>,
+    ///marks zeros, local extrema and intersections of 2d series, and lists them in the sidebar
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_analysis: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) analysis: Option<Vec<(Vec2, Feature)>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) analysis_index: Option<usize>,
+    ///models fitted to the series at the given index, drawn over the data and listed in the sidebar
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub fits: Vec<(usize, FitModel)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) fit_results: Option<Vec<Option<Fit>>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) data_hash: u64,
+    ///draws the residuals y-f(x) of each fit as points
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_residuals: bool,
+    #[allow(clippy::type_complexity)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ///evaluates the kth series at x, used to refine analysis results past the sampled data
+    pub point_eval: Option<Box<dyn Fn(usize, f64) -> Complex>>,
+    ///index of the series followed in trace mode, the cursor snaps to its nearest sample
+    ///and its value is shown, none when trace mode is off
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub trace: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) trace_index: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) zoom_box: Option<Vec2>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) zoom_history: Vec<View>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) view_history: Vec<View>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) view_pos: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) view_merge: Option<bool>,
+    ///draw a polar grid instead of a rectangular grid in conformal mode
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub conformal_polar: bool,
+    ///how many grid lines to draw in each direction in conformal mode
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub conformal_lines: usize,
     #[cfg_attr(feature = "serde", serde(default))]
     pub(crate) screen: Vec2,
     #[cfg_attr(feature = "serde", serde(default))]
@@ -435,6 +815,10 @@ pub struct Graph {
     pub(crate) side_bar_width: f64,
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) clipboard: Option<Clipboard>,
+    ///last error from saving, loading, exporting, keybinds, fonts or the clipboard,
+    ///shown at the bottom of the graph until a key is pressed
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub error: Option<RuplError>,
     #[cfg_attr(feature = "serde", serde(default))]
     pub(crate) history: Vec<Change>,
     #[cfg_attr(feature = "serde", serde(default))]
@@ -472,7 +856,8 @@ pub struct Graph {
     #[cfg_attr(feature = "serde", serde(default))]
     pub(crate) wait_frame: bool,
     #[cfg(feature = "serde")]
-    /// which file will save the serialization data
+    /// which file will save the serialization data,
+    ///human readable json if it ends in .json, compressed bitcode otherwise
     #[cfg_attr(feature = "serde", serde(default))]
     pub save_file: String,
     #[cfg(feature = "serde")]
@@ -480,10 +865,51 @@ pub struct Graph {
     pub(crate) save_num: Option<usize>,
     #[cfg(feature = "serde")]
     #[cfg_attr(feature = "serde", serde(skip))]
-    pub(crate) file_data: Option<Vec<(String, usize, String)>>,
+    pub(crate) file_data: Option<Vec<SaveEntry>>,
     #[cfg(feature = "serde")]
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) file_data_raw: Option<Vec<String>>,
+    ///text filtering the load menu by name, mode or function names
+    #[cfg(feature = "serde")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) load_filter: String,
+    ///rows of the load menu kept while a save is renamed, so it stays on its row
+    #[cfg(feature = "serde")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) load_frozen: Option<Vec<usize>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) menu_lines: Option<Vec<String>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) chord: Option<Key>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) rebind: Option<(usize, bool, Option<Key>)>,
+    #[cfg(feature = "serde")]
+    ///human editable json file which keybinds are loaded from and saved to from the rebinding menu
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub keybinds_file: String,
+    ///file the export keybinds write data to, copied to the clipboard if empty
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub export_file: String,
+    ///format the export keybinds write data in
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub export_format: ExportFormat,
+    ///longest share string in bytes the share keybind copies, dropping points until it fits
+    #[cfg(feature = "serde")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub share_limit: usize,
+    ///bits each shared value is quantized to, relative to the range of its series
+    #[cfg(feature = "serde")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub share_bits: u8,
+    ///series pasted from a share string with the name they were shared under,
+    ///kept over evaluated data until that name is edited
+    #[cfg(feature = "serde")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) shared: Vec<(usize, String, GraphType)>,
+    ///thumbnail taken from a frame passed in by the host, used by the next save
+    #[cfg(feature = "serde")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) frame_thumbnail: Vec<u8>,
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) constant_eval: Vec<(usize, String)>,
     #[cfg(any(feature = "skia", feature = "tiny-skia", feature = "wasm-draw"))]
@@ -508,6 +934,7 @@ pub enum Menu {
     Normal,
     Side,
     Settings,
+    Keybinds,
     #[cfg(feature = "serde")]
     Load,
 }
@@ -562,12 +989,17 @@ impl Default for Graph {
             name_updated: None,
             is_3d: false,
             clipboard,
+            error: None,
             #[cfg(feature = "arboard")]
             wait_frame: true,
             #[cfg(feature = "serde")]
             file_data: None,
             #[cfg(feature = "serde")]
             file_data_raw: None,
+            #[cfg(feature = "serde")]
+            load_filter: String::new(),
+            #[cfg(feature = "serde")]
+            load_frozen: None,
             image_buffer: Vec::new(),
             point_size: 5.0,
             history: Vec::new(),
@@ -584,6 +1016,21 @@ impl Default for Graph {
             save_file: String::new(),
             #[cfg(feature = "serde")]
             save_num: None,
+            menu_lines: None,
+            chord: None,
+            rebind: None,
+            #[cfg(feature = "serde")]
+            keybinds_file: String::new(),
+            export_file: String::new(),
+            export_format: ExportFormat::Csv,
+            #[cfg(feature = "serde")]
+            share_limit: 4096,
+            #[cfg(feature = "serde")]
+            share_bits: 16,
+            #[cfg(feature = "serde")]
+            shared: Vec::new(),
+            #[cfg(feature = "serde")]
+            frame_thumbnail: Vec::new(),
             cache: None,
             blacklist_graphs: Vec::new(),
             line_width: 3.0,
@@ -625,6 +1072,42 @@ impl Default for Graph {
             anti_alias: true,
             lines: Lines::Lines,
             domain_alternate: true,
+            colormap: Colormap::Viridis,
+            heatmap_range: None,
+            heatmap_bound: Vec2::new(0.0, 1.0),
+            image_dims: (0, 0),
+            show_contour: false,
+            contour_abs: Some(1.0),
+            contour_log: false,
+            contour_arg: Some(4),
+            contours: None,
+            show_zeros: false,
+            zeros: None,
+            show_help: false,
+            show_branch: false,
+            branch_color: Color::new(255, 0, 0),
+            branch_cuts: None,
+            seams: None,
+            point_index: PointIndex::default(),
+            point_click: None,
+            companions: Vec::new(),
+            show_analysis: false,
+            analysis: None,
+            analysis_index: None,
+            fits: Vec::new(),
+            fit_results: None,
+            data_hash: 0,
+            show_residuals: false,
+            point_eval: None,
+            trace: None,
+            trace_index: None,
+            zoom_box: None,
+            zoom_history: Vec::new(),
+            view_history: Vec::new(),
+            view_pos: 0,
+            view_merge: None,
+            conformal_polar: false,
+            conformal_lines: 16,
             var: Vec2::new(-2.0, 2.0),
             #[cfg(any(feature = "skia", feature = "tiny-skia", feature = "wasm-draw"))]
             request_redraw: false,
@@ -773,6 +1256,11 @@ pub struct Keybinds {
     ///delta y of ruler
     ///norm of ruler
     ///angle of ruler in degrees
+    ///and in 2d, over the x range of the ruler, for the traced series or else the first,
+    ///integral of the real part, shaded under the curve
+    ///secant slope
+    ///arc length along the curve
+    ///mean value
     pub ruler: Option<Keys>,
     ///toggles showing real/imag parts of graphs
     pub view: Option<Keys>,
@@ -790,6 +1278,10 @@ pub struct Keybinds {
     ///copys tiny serialized data to clipboard
     pub save: Option<Keys>,
     #[cfg(feature = "serde")]
+    ///copys tiny serialized data along with Coord and Point data to clipboard,
+    ///see Graph::to_share
+    pub share_data: Option<Keys>,
+    #[cfg(feature = "serde")]
     ///full saves the graph into the Graph.save_file directory
     pub full_save: Option<Keys>,
     #[cfg(feature = "serde")]
@@ -797,6 +1289,10 @@ pub struct Keybinds {
     pub paste: Option<Keys>,
     ///settings menu
     pub settings: Option<Keys>,
+    ///keybind rebinding menu
+    pub rebind: Option<Keys>,
+    ///toggles an overlay listing the keybinds relevant to the current mode
+    pub help: Option<Keys>,
     #[cfg(feature = "serde")]
     ///load from full saves
     pub load: Option<Keys>,
@@ -807,6 +1303,47 @@ pub struct Keybinds {
     pub only_real: Option<Keys>,
     ///toggles dark mode
     pub toggle_dark_mode: Option<Keys>,
+    ///toggles contour lines in domain coloring
+    pub contour: Option<Keys>,
+    ///toggles zero and pole markers in domain coloring
+    pub zeros: Option<Keys>,
+    ///copys zero and pole locations to clipboard
+    pub copy_zeros: Option<Keys>,
+    ///toggles branch cut lines in domain coloring and 3d
+    pub branch_cut: Option<Keys>,
+    ///toggles between a rectangular and polar grid in conformal mode
+    pub conformal_polar: Option<Keys>,
+    ///returns to the view from before the last box zoom
+    pub zoom_previous: Option<Keys>,
+    ///goes back to the previous view, undoing pans, zooms and mode changes
+    pub view_back: Option<Keys>,
+    ///goes forward to the next view, after going back
+    pub view_forward: Option<Keys>,
+    ///toggles trace mode, where arrow keys step along the traced series
+    pub trace: Option<Keys>,
+    ///cycles which series is traced
+    pub trace_series: Option<Keys>,
+    ///toggles markers for zeros, extrema and intersections in 2d
+    pub analysis: Option<Keys>,
+    ///selects the next zero, extremum or intersection
+    pub analysis_next: Option<Keys>,
+    ///selects the previous zero, extremum or intersection
+    pub analysis_prev: Option<Keys>,
+    ///toggles the derivative of the traced series, or the first series if not tracing
+    pub derivative: Option<Keys>,
+    ///toggles the integral of the traced series, or the first series if not tracing
+    pub integral: Option<Keys>,
+    ///cycles the fit of the traced series, or the first series if not tracing,
+    ///through linear, quadratic, cubic, exponential, power and none
+    pub fit: Option<Keys>,
+    ///toggles the residual points of fits
+    pub residuals: Option<Keys>,
+    ///copies the fit coefficients and r² to the clipboard
+    pub copy_fit: Option<Keys>,
+    ///exports all data to Graph.export_file
+    pub export_data: Option<Keys>,
+    ///exports the data inside the visible window to Graph.export_file
+    pub export_visible: Option<Keys>,
 }
 #[cfg(feature = "serde")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -830,6 +1367,31 @@ pub struct GraphTiny {
     pub view_x: bool,
     pub graph_mode: GraphMode,
     pub only_real: bool,
+    ///Coord and Point series shared along with the names, see Graph::to_share
+    pub data: Vec<TinySeries>,
+}
+///values stored as start+step*n, with each n written as its difference from the previous one
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct TinyAxis {
+    pub start: f64,
+    pub step: f64,
+    ///differences between successive n, i64::MIN for a missing value
+    pub deltas: Vec<i64>,
+}
+///a Coord or Point series of GraphTiny, quantized and delta encoded
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct TinySeries {
+    ///which series of Graph.data it fills
+    pub index: usize,
+    ///if the samples are Point data rather than a Coord series
+    pub points: bool,
+    pub x: TinyAxis,
+    ///real parts, none if every sample is imaginary
+    pub re: Option<TinyAxis>,
+    ///imaginary parts, none if every sample is real
+    pub im: Option<TinyAxis>,
 }
 #[cfg(feature = "serde")]
 impl Graph {
@@ -863,8 +1425,10 @@ impl Graph {
             view_x: self.view_x,
             graph_mode: self.graph_mode,
             only_real: self.only_real,
+            data: Vec::new(),
         }
     }
+    ///applies the view and names of a GraphTiny, along with any shared data
     pub fn apply_tiny(&mut self, tiny: GraphTiny) {
         self.names = tiny.names;
         self.bound = tiny.bound.into();
@@ -884,28 +1448,73 @@ impl Graph {
         self.view_x = tiny.view_x;
         self.graph_mode = tiny.graph_mode;
         self.only_real = tiny.only_real;
-        self.recalculate(None);
-        self.name_modified(None);
+        self.apply_tiny_data(tiny.data);
+        if !self.all_shared() {
+            self.recalculate(None);
+            self.name_modified(None);
+        }
         self.text_box = Some((0, 0));
     }
 }
 #[cfg(feature = "serde")]
 impl TryFrom<&String> for GraphTiny {
-    type Error = ();
+    type Error = RuplError;
     fn try_from(value: &String) -> Result<Self, Self::Error> {
-        let (a, b) = value.rsplit_once('@').ok_or(())?;
+        GraphTiny::decode(value)
+    }
+}
+#[cfg(feature = "serde")]
+impl GraphTiny {
+    ///reads either the compressed form or the json form, migrating older versions,
+    ///rejecting declared lengths and shared data past their caps
+    pub fn decode(value: &str) -> Result<Self, RuplError> {
+        let decode = |e: &dyn std::fmt::Display| RuplError::Decode(e.to_string());
+        if value.trim_start().starts_with('{') {
+            let v = serde_json::from_str::<serde_json::Value>(value).map_err(|e| decode(&e))?;
+            crate::migrate::check_version(v["version"].as_u64().unwrap_or(0) as u32)?;
+            let tiny = serde_json::from_value::<GraphTiny>(v).map_err(|e| decode(&e))?;
+            tiny.check_data()?;
+            return Ok(tiny);
+        }
+        let (v, value) = crate::migrate::split_version(value);
+        let (a, b) = value
+            .rsplit_once('@')
+            .ok_or_else(|| decode(&"missing length"))?;
         let l = base64::prelude::BASE64_URL_SAFE_NO_PAD
             .decode(a)
-            .map_err(|_| ())?;
+            .map_err(|e| decode(&e))?;
         let l = String::from_utf8(l)
-            .map_err(|_| ())?
+            .map_err(|e| decode(&e))?
             .parse::<usize>()
-            .map_err(|_| ())?;
+            .map_err(|e| decode(&e))?;
         let comp = base64::prelude::BASE64_URL_SAFE_NO_PAD
             .decode(b)
-            .map_err(|_| ())?;
-        let seri = zstd::bulk::decompress(&comp, l).map_err(|_| ())?;
-        bitcode::deserialize(&seri).map_err(|_| ())
+            .map_err(|e| decode(&e))?;
+        let seri = crate::migrate::decompress(&comp, l)?;
+        let tiny = crate::migrate::decode_tiny(v, &seri)?;
+        tiny.check_data()?;
+        Ok(tiny)
+    }
+}
+#[cfg(feature = "serde")]
+impl From<&GraphTiny> for String {
+    ///compressed form of v1@len@base64(zstd(bitcode)), as copied by the save keybind
+    fn from(value: &GraphTiny) -> Self {
+        let seri = bitcode::serialize(value).unwrap();
+        let l = seri.len();
+        let comp = zstd::bulk::compress(&seri, 22).unwrap();
+        let s = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(&comp);
+        let l = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(l.to_string());
+        format!("v{}@{l}@{s}", crate::migrate::SAVE_VERSION)
+    }
+}
+#[cfg(feature = "serde")]
+impl GraphTiny {
+    ///human readable json form with its version, read back by GraphTiny::try_from like the compressed form
+    pub fn to_json(&self) -> String {
+        let mut v = serde_json::to_value(self).unwrap();
+        v["version"] = crate::migrate::SAVE_VERSION.into();
+        serde_json::to_string_pretty(&v).unwrap()
     }
 }
 impl Default for Keybinds {
@@ -970,6 +1579,11 @@ impl Default for Keybinds {
             #[cfg(feature = "serde")]
             save: Some(Keys::new_with_modifier(Key::S, Modifiers::default().ctrl())),
             #[cfg(feature = "serde")]
+            share_data: Some(Keys::new_with_modifier(
+                Key::S,
+                Modifiers::default().ctrl().alt(),
+            )),
+            #[cfg(feature = "serde")]
             full_save: Some(Keys::new_with_modifier(
                 Key::S,
                 Modifiers::default().ctrl().shift(),
@@ -1017,10 +1631,15 @@ impl Default for Keybinds {
             reset: Some(Keys::new(Key::T)),
             side: Some(Keys::new(Key::Escape)),
             fast: Some(Keys::new(Key::F)),
-            settings: None, /*Some(Keys::new_with_modifier( TODO
-                                Key::Escape,
-                                Modifiers::default().ctrl(),
-                            ))*/
+            settings: Some(Keys::new_with_modifier(
+                Key::Escape,
+                Modifiers::default().ctrl(),
+            )),
+            rebind: Some(Keys::new_with_modifier(
+                Key::Escape,
+                Modifiers::default().alt(),
+            )),
+            help: Some(Keys::new(Key::F1)),
             #[cfg(feature = "serde")]
             load: Some(Keys::new_with_modifier(
                 Key::Escape,
@@ -1036,6 +1655,50 @@ impl Default for Keybinds {
                 Key::O,
                 Modifiers::default().shift().ctrl(),
             )),
+            contour: Some(Keys::new(Key::K)),
+            zeros: Some(Keys::new(Key::J)),
+            copy_zeros: Some(Keys::new_with_modifier(Key::J, Modifiers::default().ctrl())),
+            branch_cut: Some(Keys::new(Key::H)),
+            conformal_polar: Some(Keys::new(Key::G)),
+            zoom_previous: Some(Keys::new(Key::Backspace)),
+            view_back: Some(Keys::new_with_modifier(
+                Key::ArrowLeft,
+                Modifiers::default().alt(),
+            )),
+            view_forward: Some(Keys::new_with_modifier(
+                Key::ArrowRight,
+                Modifiers::default().alt(),
+            )),
+            trace: Some(Keys::new(Key::Q)),
+            trace_series: Some(Keys::new_with_modifier(
+                Key::Q,
+                Modifiers::default().shift(),
+            )),
+            analysis: Some(Keys::new(Key::A)),
+            analysis_next: Some(Keys::new_with_modifier(
+                Key::A,
+                Modifiers::default().shift(),
+            )),
+            analysis_prev: Some(Keys::new_with_modifier(
+                Key::A,
+                Modifiers::default().shift().ctrl(),
+            )),
+            derivative: Some(Keys::new(Key::D)),
+            integral: Some(Keys::new_with_modifier(
+                Key::D,
+                Modifiers::default().shift(),
+            )),
+            fit: Some(Keys::new(Key::M)),
+            residuals: Some(Keys::new_with_modifier(
+                Key::M,
+                Modifiers::default().shift(),
+            )),
+            copy_fit: Some(Keys::new_with_modifier(Key::M, Modifiers::default().ctrl())),
+            export_data: Some(Keys::new(Key::E)),
+            export_visible: Some(Keys::new_with_modifier(
+                Key::E,
+                Modifiers::default().shift(),
+            )),
         }
     }
 }
@@ -1167,7 +1830,9 @@ impl From<&egui::InputState> for InputState {
 }
 impl InputState {
     pub(crate) fn keys_pressed(&self, keys: Option<Keys>) -> bool {
-        if let Some(keys) = keys {
+        if let Some(keys) = keys
+            && keys.prefix.is_none()
+        {
             keys.modifiers
                 .map(|m| self.modifiers == m)
                 .unwrap_or(self.modifiers.is_false())
@@ -1181,20 +1846,33 @@ impl InputState {
 #[derive(Copy, Debug, Clone, PartialEq)]
 pub struct Keys {
     ///None is equivalent to a set of false Modifiers
-    modifiers: Option<Modifiers>,
-    key: Key,
+    pub(crate) modifiers: Option<Modifiers>,
+    pub(crate) key: Key,
+    ///key which has to be pressed, without modifiers, right before this one
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) prefix: Option<Key>,
 }
 impl Keys {
     pub fn new(key: Key) -> Self {
         Self {
             key,
             modifiers: None,
+            prefix: None,
         }
     }
     pub fn new_with_modifier(key: Key, modifiers: Modifiers) -> Self {
         Self {
             key,
             modifiers: Some(modifiers),
+            prefix: None,
+        }
+    }
+    ///a chord, prefix followed by key, such as g then z
+    pub fn new_chord(prefix: Key, key: Key) -> Self {
+        Self {
+            key,
+            modifiers: None,
+            prefix: Some(prefix),
         }
     }
 }
@@ -1270,6 +1948,17 @@ impl Color {
     pub(crate) fn splat(c: u8) -> Self {
         Self { r: c, g: c, b: c }
     }
+    pub(crate) fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+    pub(crate) fn from_hex(s: &str) -> Option<Self> {
+        let s = s.strip_prefix('#').unwrap_or(s);
+        if s.len() != 6 || !s.is_ascii() {
+            return None;
+        }
+        let c = |i: usize| u8::from_str_radix(&s[i..i + 2], 16).ok();
+        Some(Self::new(c(0)?, c(2)?, c(4)?))
+    }
     #[cfg(feature = "wasm-draw")]
     pub(crate) fn to_col(self) -> String {
         format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
@@ -1438,7 +2127,7 @@ impl From<(f64, f64)> for Complex {
     }
 }
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Copy, Debug, Clone, Default)]
+#[derive(Copy, Debug, Clone, PartialEq, Default)]
 pub struct Vec3 {
     pub x: f64,
     pub y: f64,
@@ -1557,7 +2246,6 @@ pub(crate) enum Align {
     LeftBottom,
     LeftCenter,
     LeftTop,
-    #[allow(dead_code)]
     CenterBottom,
     #[allow(dead_code)]
     CenterCenter,